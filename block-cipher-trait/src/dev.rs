@@ -4,12 +4,16 @@ macro_rules! new_test {
     ($name:ident, $test_name:expr, $cipher:ty) => {
         #[test]
         fn $name() {
-            use block_cipher_trait::BlockCipher;
+            use block_cipher_trait::{
+                BlockCipherBase, BlockEncrypt, BlockDecrypt, NewBlockCipher,
+                DynBlockCipher,
+            };
             use block_cipher_trait::generic_array::GenericArray;
             use block_cipher_trait::generic_array::typenum::Unsigned;
 
             fn run_test(key: &[u8], pt: &[u8], ct: &[u8]) -> bool {
-                let state = <$cipher as BlockCipher>::new_varkey(key).unwrap();
+                let state = <$cipher as NewBlockCipher>::new_varkey(key)
+                    .unwrap();
 
                 let mut block = GenericArray::clone_from_slice(pt);
                 state.encrypt_block(&mut block);
@@ -22,16 +26,39 @@ macro_rules! new_test {
                     return false;
                 }
 
+                // check that key size metadata accepts the test key
+                if <$cipher as NewBlockCipher>::check_key_size(key.len())
+                    .is_err()
+                {
+                    return false;
+                }
+
+                // check that cipher works through the trait object
+                let state: &DynBlockCipher = &state;
+                let (min, max) = (state.min_key_size(), state.max_key_size());
+                if state.key_size() < min || state.key_size() > max {
+                    return false;
+                }
+                state.encrypt(&mut block);
+                if ct != block.as_slice() {
+                    return false;
+                }
+                state.decrypt(&mut block);
+                if pt != block.as_slice() {
+                    return false;
+                }
+
                 true
             }
 
             fn run_par_test(key: &[u8], pt: &[u8], ct: &[u8]) -> bool {
-                type ParBlocks = <$cipher as BlockCipher>::ParBlocks;
-                type BlockSize = <$cipher as BlockCipher>::BlockSize;
+                type ParBlocks = <$cipher as BlockCipherBase>::ParBlocks;
+                type BlockSize = <$cipher as BlockCipherBase>::BlockSize;
                 type Block = GenericArray<u8, BlockSize>;
                 type ParBlock = GenericArray<Block, ParBlocks>;
 
-                let state = <$cipher as BlockCipher>::new_varkey(key).unwrap();
+                let state = <$cipher as NewBlockCipher>::new_varkey(key)
+                    .unwrap();
 
                 let block = Block::clone_from_slice(pt);
                 let mut blocks1 = ParBlock::default();
@@ -56,6 +83,61 @@ macro_rules! new_test {
                 true
             }
 
+            fn run_slice_test(key: &[u8], pt: &[u8]) -> bool {
+                use block_cipher_trait::{to_blocks, to_blocks_ref};
+
+                let state = <$cipher as NewBlockCipher>::new_varkey(key)
+                    .unwrap();
+                let bs = pt.len();
+                let pb = <$cipher as BlockCipherBase>::ParBlocks::to_usize();
+                let mut pts = [0u8; 4096];
+                let mut cts = [0u8; 4096];
+                let mut buf = [0u8; 4096];
+
+                // check that in-place and buffer-to-buffer slice methods
+                // and `encrypt_block` result in the same ciphertext (and the
+                // same for decryption) for 0, ParBlocks - 1, ParBlocks and
+                // 2*ParBlocks + 1 blocks
+                for &n in [0, pb - 1, pb, 2*pb + 1].iter() {
+                    let len = n*bs;
+                    assert!(len <= buf.len(), "too many parallel blocks");
+                    let pts = &mut pts[..len];
+                    let cts = &mut cts[..len];
+                    let buf = &mut buf[..len];
+                    for (i, b) in pts.chunks_mut(bs).enumerate() {
+                        b.copy_from_slice(pt);
+                        b[0] = b[0].wrapping_add(i as u8);
+                    }
+                    cts.copy_from_slice(pts);
+                    for b in to_blocks(cts) { state.encrypt_block(b); }
+
+                    buf.copy_from_slice(pts);
+                    state.encrypt_slice(to_blocks(buf));
+                    if buf[..] != cts[..] { return false; }
+                    state.decrypt_slice(to_blocks(buf));
+                    if buf[..] != pts[..] { return false; }
+
+                    state.encrypt_bytes(buf);
+                    if buf[..] != cts[..] { return false; }
+                    state.decrypt_bytes(buf);
+                    if buf[..] != pts[..] { return false; }
+
+                    // check that buffer-to-buffer methods give the same
+                    // result as in-place ones
+                    state.encrypt_bytes_b2b(pts, buf);
+                    if buf[..] != cts[..] { return false; }
+                    state.decrypt_bytes_b2b(cts, buf);
+                    if buf[..] != pts[..] { return false; }
+
+                    state.encrypt_slice_b2b(to_blocks_ref(pts), to_blocks(buf));
+                    if buf[..] != cts[..] { return false; }
+                    state.decrypt_slice_b2b(to_blocks_ref(cts), to_blocks(buf));
+                    if buf[..] != pts[..] { return false; }
+                }
+
+                true
+            }
+
             let keys = include_bytes!(
                 concat!("data/", $test_name, ".keys.bin"));
             let plaintexts = include_bytes!(
@@ -93,7 +175,7 @@ macro_rules! new_test {
                 }
 
                 /// test parallel blocks encryption/decryption
-                let pb = <$cipher as BlockCipher>::ParBlocks::to_usize();
+                let pb = <$cipher as BlockCipherBase>::ParBlocks::to_usize();
                 if pb != 1 {
                     if !run_par_test(key, plaintext, ciphertext) {
                         panic!("\n\
@@ -107,6 +189,17 @@ macro_rules! new_test {
                         );
                     }
                 }
+
+                // test encryption/decryption of slices of blocks
+                if !run_slice_test(key, plaintext) {
+                    panic!("\n\
+                        Failed slice test №{}\n\
+                        key: [{}..{}]\t{:?}\n\
+                        plaintext: [{}..{}]\t{:?}\n",
+                        i, idx[0][0], idx[0][1], key,
+                        idx[1][0], idx[1][1], plaintext,
+                    );
+                }
             }
         }
     }
@@ -118,7 +211,7 @@ macro_rules! bench {
         extern crate test;
 
         use test::Bencher;
-        use block_cipher_trait::BlockCipher;
+        use block_cipher_trait::{BlockEncrypt, BlockDecrypt, NewBlockCipher};
 
         #[bench]
         pub fn encrypt(bh: &mut Bencher) {
@@ -0,0 +1,96 @@
+use generic_array::typenum::Unsigned;
+#[cfg(feature = "std")]
+use std::boxed::Box;
+
+use {BlockCipher, BlockCipherBase, NewBlockCipher};
+#[cfg(feature = "std")]
+use InvalidKey;
+
+/// Object-safe block cipher trait.
+///
+/// It's automatically implemented for all types which implement
+/// `BlockCipher` and allows to work with ciphers selected at runtime via
+/// trait objects, e.g. `Box<DynBlockCipher>`.
+pub trait DynBlockCipher {
+    /// Return block size in bytes
+    fn block_size(&self) -> usize;
+
+    /// Return key size in bytes with which cipher guaranteed to be
+    /// initialized
+    fn key_size(&self) -> usize;
+
+    /// Return minimum key size in bytes accepted by `new_varkey`
+    fn min_key_size(&self) -> usize;
+
+    /// Return maximum key size in bytes accepted by `new_varkey`
+    fn max_key_size(&self) -> usize;
+
+    /// Return step between key sizes in bytes accepted by `new_varkey`
+    fn key_size_step(&self) -> usize;
+
+    /// Encrypt data in-place.
+    ///
+    /// # Panics
+    /// If length of `data` is not a multiple of the block size.
+    fn encrypt(&self, data: &mut [u8]);
+
+    /// Decrypt data in-place.
+    ///
+    /// # Panics
+    /// If length of `data` is not a multiple of the block size.
+    fn decrypt(&self, data: &mut [u8]);
+}
+
+impl<C: BlockCipher> DynBlockCipher for C {
+    #[inline]
+    fn block_size(&self) -> usize {
+        <C as BlockCipherBase>::BlockSize::to_usize()
+    }
+
+    #[inline]
+    fn key_size(&self) -> usize {
+        <C as NewBlockCipher>::KeySize::to_usize()
+    }
+
+    #[inline]
+    fn min_key_size(&self) -> usize {
+        C::MIN_KEY_SIZE
+    }
+
+    #[inline]
+    fn max_key_size(&self) -> usize {
+        C::MAX_KEY_SIZE
+    }
+
+    #[inline]
+    fn key_size_step(&self) -> usize {
+        C::KEY_SIZE_STEP
+    }
+
+    #[inline]
+    fn encrypt(&self, data: &mut [u8]) {
+        self.encrypt_bytes(data);
+    }
+
+    #[inline]
+    fn decrypt(&self, data: &mut [u8]) {
+        self.decrypt_bytes(data);
+    }
+}
+
+/// Create boxed block cipher instance from key with variable size using
+/// `NewBlockCipher::new_varkey`.
+///
+/// On invalid key length returned `InvalidKey::Length` error describes the
+/// range of key sizes accepted by the cipher.
+///
+/// Function pointers to its monomorphizations (e.g. `new_dyn::<Aes128>`)
+/// can be used to build a table of ciphers selectable at runtime.
+#[cfg(feature = "std")]
+pub fn new_dyn<C>(key: &[u8])
+    -> Result<Box<DynBlockCipher>, InvalidKey>
+    where C: BlockCipher + 'static
+{
+    let cipher = C::new_varkey(key)?;
+    Ok(Box::new(cipher))
+}
@@ -2,19 +2,48 @@ use core::fmt;
 #[cfg(feature = "std")]
 use std::error;
 
-/// Error struct which used with `NewVarKey`
+/// Error type which is returned by `new_varkey` on invalid key
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct InvalidKeyLength;
+pub enum InvalidKey {
+    /// Key has length which is not accepted by the cipher
+    Length {
+        /// Length of the rejected key in bytes
+        len: usize,
+        /// Minimum accepted key length in bytes
+        min: usize,
+        /// Maximum accepted key length in bytes
+        max: usize,
+        /// Step between accepted key lengths in bytes
+        step: usize,
+    },
+    /// Key is known to be weak and was rejected by the cipher
+    Weak,
+}
 
-impl fmt::Display for InvalidKeyLength {
+impl fmt::Display for InvalidKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("invalid key length")
+        match *self {
+            InvalidKey::Length { len, min, max, step } => {
+                write!(f, "invalid key length {}, ", len)?;
+                if min == max {
+                    write!(f, "expected {}", min)
+                } else if step == 1 {
+                    write!(f, "expected {}..={}", min, max)
+                } else {
+                    write!(f, "expected {}..={} with step {}", min, max, step)
+                }
+            },
+            InvalidKey::Weak => f.write_str("weak key"),
+        }
     }
 }
 
 #[cfg(feature = "std")]
-impl error::Error for InvalidKeyLength {
+impl error::Error for InvalidKey {
     fn description(&self) -> &str {
-        "invalid key length"
+        match *self {
+            InvalidKey::Length { .. } => "invalid key length",
+            InvalidKey::Weak => "weak key",
+        }
     }
 }
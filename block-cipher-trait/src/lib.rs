@@ -1,5 +1,12 @@
 //! This crate defines a set of simple traits used to define functionality of
 //! block ciphers.
+//!
+//! Ciphers implement `NewBlockCipher`, `BlockCipherBase` and one or both of
+//! `BlockEncrypt` and `BlockDecrypt`. `BlockCipher` is implemented
+//! automatically for ciphers which support both directions.
+//!
+//! `DynBlockCipher` is an object-safe counterpart of `BlockCipher` which can
+//! be used when cipher is selected at runtime.
 #![no_std]
 pub extern crate generic_array;
 
@@ -8,25 +15,80 @@ extern crate std;
 
 use generic_array::{GenericArray, ArrayLength};
 use generic_array::typenum::Unsigned;
+use core::slice;
 
 mod errors;
+mod dyn_cipher;
 #[cfg(feature = "dev")]
 pub mod dev;
 
-pub use errors::InvalidKeyLength;
+#[allow(deprecated)]
+pub use errors::InvalidKey;
+pub use dyn_cipher::DynBlockCipher;
+#[cfg(feature = "std")]
+pub use dyn_cipher::new_dyn;
 
 type ParBlocks<B, P> = GenericArray<GenericArray<u8, B>, P>;
 
-/// The trait which defines in-place encryption and decryption
-/// over single block or several blocks in parallel.
-pub trait BlockCipher: core::marker::Sized {
-    /// Key size in bytes with which cipher guaranteed to be initialized
-    type KeySize: ArrayLength<u8>;
+/// Reinterpret byte slice as a slice of blocks.
+///
+/// # Panics
+/// If length of `data` is not a multiple of the block size.
+#[inline(always)]
+pub fn to_blocks_ref<N>(data: &[u8]) -> &[GenericArray<u8, N>]
+    where N: ArrayLength<u8>
+{
+    let n = N::to_usize();
+    assert_eq!(data.len() % n, 0, "invalid data length");
+    // `GenericArray<u8, N>` has the same layout as `[u8; N]`
+    unsafe {
+        slice::from_raw_parts(
+            data.as_ptr() as *const GenericArray<u8, N>,
+            data.len() / n,
+        )
+    }
+}
+
+/// Reinterpret mutable byte slice as a slice of blocks.
+///
+/// # Panics
+/// If length of `data` is not a multiple of the block size.
+#[inline(always)]
+pub fn to_blocks<N>(data: &mut [u8]) -> &mut [GenericArray<u8, N>]
+    where N: ArrayLength<u8>
+{
+    let n = N::to_usize();
+    assert_eq!(data.len() % n, 0, "invalid data length");
+    // `GenericArray<u8, N>` has the same layout as `[u8; N]`
+    unsafe {
+        slice::from_raw_parts_mut(
+            data.as_mut_ptr() as *mut GenericArray<u8, N>,
+            data.len() / n,
+        )
+    }
+}
+
+/// Block cipher parameters shared by encryption and decryption traits.
+pub trait BlockCipherBase {
     /// Size of the block in bytes
     type BlockSize: ArrayLength<u8>;
     /// Number of blocks which can be processed in parallel by
     /// cipher implementation
     type ParBlocks: ArrayLength<GenericArray<u8, Self::BlockSize>>;
+}
+
+/// Block cipher creation trait.
+pub trait NewBlockCipher: core::marker::Sized {
+    /// Key size in bytes with which cipher guaranteed to be initialized
+    type KeySize: ArrayLength<u8>;
+
+    /// Minimum key size in bytes accepted by `new_varkey`
+    const MIN_KEY_SIZE: usize = Self::KeySize::USIZE;
+    /// Maximum key size in bytes accepted by `new_varkey`
+    const MAX_KEY_SIZE: usize = Self::KeySize::USIZE;
+    /// Step between key sizes in bytes accepted by `new_varkey`, zero
+    /// means that only `MIN_KEY_SIZE` is accepted
+    const KEY_SIZE_STEP: usize = 1;
 
     /// Create new block cipher instance from key with fixed size.
     fn new(key: &GenericArray<u8, Self::KeySize>) -> Self;
@@ -34,21 +96,55 @@ pub trait BlockCipher: core::marker::Sized {
     /// Create new block cipher instance from key with variable size.
     ///
     /// Default implementation will accept only keys with length equal to
-    /// `KeySize`, but some ciphers can accept range of key lengths.
-    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKeyLength> {
+    /// `KeySize`, but some ciphers can accept range of key lengths. Such
+    /// ciphers must override key size constants and this method, which is
+    /// checked in debug builds.
+    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey> {
+        debug_assert!(
+            Self::MIN_KEY_SIZE == Self::KeySize::USIZE
+                && Self::MAX_KEY_SIZE == Self::KeySize::USIZE,
+            "ciphers with key size range must override `new_varkey`"
+        );
+        Self::check_key_size(key.len())?;
         if key.len() != Self::KeySize::to_usize() {
-            Err(InvalidKeyLength)
+            return Err(Self::invalid_key_length(key.len()));
+        }
+        Ok(Self::new(GenericArray::from_slice(key)))
+    }
+
+    /// Check if key length `len` lies in the range described by the key
+    /// size constants.
+    fn check_key_size(len: usize) -> Result<(), InvalidKey> {
+        let (min, max) = (Self::MIN_KEY_SIZE, Self::MAX_KEY_SIZE);
+        let valid = len >= min && len <= max && match Self::KEY_SIZE_STEP {
+            0 => len == min,
+            step => (len - min) % step == 0,
+        };
+        if valid {
+            Ok(())
         } else {
-            Ok(Self::new(GenericArray::from_slice(key)))
+            Err(Self::invalid_key_length(len))
         }
     }
 
+    /// Create `InvalidKey::Length` error for key length `len` using the key
+    /// size constants.
+    fn invalid_key_length(len: usize) -> InvalidKey {
+        InvalidKey::Length {
+            len,
+            min: Self::MIN_KEY_SIZE,
+            max: Self::MAX_KEY_SIZE,
+            step: Self::KEY_SIZE_STEP,
+        }
+    }
+}
+
+/// The trait which defines in-place encryption over single block or several
+/// blocks in parallel.
+pub trait BlockEncrypt: BlockCipherBase {
     /// Encrypt block in-place
     fn encrypt_block(&self, block: &mut GenericArray<u8, Self::BlockSize>);
 
-    /// Decrypt block in-place
-    fn decrypt_block(&self, block: &mut GenericArray<u8, Self::BlockSize>);
-
     /// Encrypt several blocks in parallel using instruction level parallelism
     /// if possible.
     ///
@@ -60,6 +156,102 @@ pub trait BlockCipher: core::marker::Sized {
         for block in blocks.iter_mut() { self.encrypt_block(block); }
     }
 
+    /// Encrypt slice of blocks of arbitrary length.
+    ///
+    /// Full chunks of `ParBlocks` blocks are encrypted using
+    /// `encrypt_blocks`, while the remaining blocks using `encrypt_block`.
+    #[inline]
+    fn encrypt_slice(&self,
+        blocks: &mut [GenericArray<u8, Self::BlockSize>])
+    {
+        let pb = Self::ParBlocks::to_usize();
+        for chunk in blocks.chunks_mut(pb) {
+            if chunk.len() == pb {
+                self.encrypt_blocks(GenericArray::from_mut_slice(chunk));
+            } else {
+                for block in chunk { self.encrypt_block(block); }
+            }
+        }
+    }
+
+    /// Encrypt data in-place, it's equivalent to `encrypt_slice` over
+    /// blocks of `data`.
+    ///
+    /// # Panics
+    /// If length of `data` is not a multiple of the block size.
+    #[inline]
+    fn encrypt_bytes(&self, data: &mut [u8]) {
+        self.encrypt_slice(to_blocks(data));
+    }
+
+    /// Encrypt block from `in_block` into `out_block`.
+    ///
+    /// Default implementation copies `in_block` into `out_block` and
+    /// encrypts it in-place.
+    #[inline]
+    fn encrypt_block_b2b(&self,
+        in_block: &GenericArray<u8, Self::BlockSize>,
+        out_block: &mut GenericArray<u8, Self::BlockSize>)
+    {
+        out_block.clone_from_slice(in_block);
+        self.encrypt_block(out_block);
+    }
+
+    /// Encrypt several blocks in parallel from `in_blocks` into `out_blocks`.
+    #[inline]
+    fn encrypt_blocks_b2b(&self,
+        in_blocks: &ParBlocks<Self::BlockSize, Self::ParBlocks>,
+        out_blocks: &mut ParBlocks<Self::BlockSize, Self::ParBlocks>)
+    {
+        out_blocks.clone_from_slice(in_blocks);
+        self.encrypt_blocks(out_blocks);
+    }
+
+    /// Encrypt slice of blocks of arbitrary length from `in_blocks` into
+    /// `out_blocks`.
+    ///
+    /// # Panics
+    /// If `in_blocks` and `out_blocks` have different lengths.
+    #[inline]
+    fn encrypt_slice_b2b(&self,
+        in_blocks: &[GenericArray<u8, Self::BlockSize>],
+        out_blocks: &mut [GenericArray<u8, Self::BlockSize>])
+    {
+        assert_eq!(in_blocks.len(), out_blocks.len());
+        let pb = Self::ParBlocks::to_usize();
+        let chunks = in_blocks.chunks(pb).zip(out_blocks.chunks_mut(pb));
+        for (in_chunk, out_chunk) in chunks {
+            if in_chunk.len() == pb {
+                self.encrypt_blocks_b2b(
+                    GenericArray::from_slice(in_chunk),
+                    GenericArray::from_mut_slice(out_chunk),
+                );
+            } else {
+                for (i, o) in in_chunk.iter().zip(out_chunk.iter_mut()) {
+                    self.encrypt_block_b2b(i, o);
+                }
+            }
+        }
+    }
+
+    /// Encrypt data from `input` into `output`, it's equivalent to
+    /// `encrypt_slice_b2b` over blocks of `input` and `output`.
+    ///
+    /// # Panics
+    /// If `input` and `output` have different lengths or if their length is
+    /// not a multiple of the block size.
+    #[inline]
+    fn encrypt_bytes_b2b(&self, input: &[u8], output: &mut [u8]) {
+        self.encrypt_slice_b2b(to_blocks_ref(input), to_blocks(output));
+    }
+}
+
+/// The trait which defines in-place decryption over single block or several
+/// blocks in parallel.
+pub trait BlockDecrypt: BlockCipherBase {
+    /// Decrypt block in-place
+    fn decrypt_block(&self, block: &mut GenericArray<u8, Self::BlockSize>);
+
     /// Decrypt several blocks in parallel using instruction level parallelism
     /// if possible.
     ///
@@ -70,4 +262,124 @@ pub trait BlockCipher: core::marker::Sized {
     {
         for block in blocks.iter_mut() { self.decrypt_block(block); }
     }
+
+    /// Decrypt slice of blocks of arbitrary length.
+    ///
+    /// Full chunks of `ParBlocks` blocks are decrypted using
+    /// `decrypt_blocks`, while the remaining blocks using `decrypt_block`.
+    #[inline]
+    fn decrypt_slice(&self,
+        blocks: &mut [GenericArray<u8, Self::BlockSize>])
+    {
+        let pb = Self::ParBlocks::to_usize();
+        for chunk in blocks.chunks_mut(pb) {
+            if chunk.len() == pb {
+                self.decrypt_blocks(GenericArray::from_mut_slice(chunk));
+            } else {
+                for block in chunk { self.decrypt_block(block); }
+            }
+        }
+    }
+
+    /// Decrypt data in-place, it's equivalent to `decrypt_slice` over
+    /// blocks of `data`.
+    ///
+    /// # Panics
+    /// If length of `data` is not a multiple of the block size.
+    #[inline]
+    fn decrypt_bytes(&self, data: &mut [u8]) {
+        self.decrypt_slice(to_blocks(data));
+    }
+
+    /// Decrypt block from `in_block` into `out_block`.
+    ///
+    /// Default implementation copies `in_block` into `out_block` and
+    /// decrypts it in-place.
+    #[inline]
+    fn decrypt_block_b2b(&self,
+        in_block: &GenericArray<u8, Self::BlockSize>,
+        out_block: &mut GenericArray<u8, Self::BlockSize>)
+    {
+        out_block.clone_from_slice(in_block);
+        self.decrypt_block(out_block);
+    }
+
+    /// Decrypt several blocks in parallel from `in_blocks` into `out_blocks`.
+    #[inline]
+    fn decrypt_blocks_b2b(&self,
+        in_blocks: &ParBlocks<Self::BlockSize, Self::ParBlocks>,
+        out_blocks: &mut ParBlocks<Self::BlockSize, Self::ParBlocks>)
+    {
+        out_blocks.clone_from_slice(in_blocks);
+        self.decrypt_blocks(out_blocks);
+    }
+
+    /// Decrypt slice of blocks of arbitrary length from `in_blocks` into
+    /// `out_blocks`.
+    ///
+    /// # Panics
+    /// If `in_blocks` and `out_blocks` have different lengths.
+    #[inline]
+    fn decrypt_slice_b2b(&self,
+        in_blocks: &[GenericArray<u8, Self::BlockSize>],
+        out_blocks: &mut [GenericArray<u8, Self::BlockSize>])
+    {
+        assert_eq!(in_blocks.len(), out_blocks.len());
+        let pb = Self::ParBlocks::to_usize();
+        let chunks = in_blocks.chunks(pb).zip(out_blocks.chunks_mut(pb));
+        for (in_chunk, out_chunk) in chunks {
+            if in_chunk.len() == pb {
+                self.decrypt_blocks_b2b(
+                    GenericArray::from_slice(in_chunk),
+                    GenericArray::from_mut_slice(out_chunk),
+                );
+            } else {
+                for (i, o) in in_chunk.iter().zip(out_chunk.iter_mut()) {
+                    self.decrypt_block_b2b(i, o);
+                }
+            }
+        }
+    }
+
+    /// Decrypt data from `input` into `output`, it's equivalent to
+    /// `decrypt_slice_b2b` over blocks of `input` and `output`.
+    ///
+    /// # Panics
+    /// If `input` and `output` have different lengths or if their length is
+    /// not a multiple of the block size.
+    #[inline]
+    fn decrypt_bytes_b2b(&self, input: &[u8], output: &mut [u8]) {
+        self.decrypt_slice_b2b(to_blocks_ref(input), to_blocks(output));
+    }
 }
+
+/// Tweakable block cipher trait.
+///
+/// In addition to the key, result of encryption and decryption depends on a
+/// public tweak value, e.g. sector number in disk encryption.
+pub trait TweakableBlockCipher: BlockCipherBase {
+    /// Size of the tweak in bytes
+    type TweakSize: ArrayLength<u8>;
+
+    /// Encrypt block in-place using the given tweak
+    fn encrypt_block_with_tweak(&self,
+        block: &mut GenericArray<u8, Self::BlockSize>,
+        tweak: &GenericArray<u8, Self::TweakSize>);
+
+    /// Decrypt block in-place using the given tweak
+    fn decrypt_block_with_tweak(&self,
+        block: &mut GenericArray<u8, Self::BlockSize>,
+        tweak: &GenericArray<u8, Self::TweakSize>);
+}
+
+/// Block cipher which can be initialized from a key and supports both
+/// encryption and decryption.
+///
+/// This trait is automatically implemented for all types which implement
+/// `NewBlockCipher`, `BlockEncrypt` and `BlockDecrypt`. Code which needs
+/// only one direction should require `BlockEncrypt` or `BlockDecrypt`
+/// instead, so it can be used with ciphers which do not compute key schedule
+/// for the other direction.
+pub trait BlockCipher: NewBlockCipher + BlockEncrypt + BlockDecrypt {}
+
+impl<C: NewBlockCipher + BlockEncrypt + BlockDecrypt> BlockCipher for C {}
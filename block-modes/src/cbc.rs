@@ -0,0 +1,56 @@
+use block_cipher_trait::BlockCipher;
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::Unsigned;
+use utils::{Block, ParBlocks, xor};
+use BlockMode;
+
+/// [Cipher Block Chaining][1] (CBC) block cipher mode instance.
+///
+/// Encryption is sequential, while decryption processes `ParBlocks`
+/// blocks at once.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#CBC
+pub struct Cbc<C: BlockCipher> {
+    cipher: C,
+    iv: Block<C>,
+}
+
+impl<C: BlockCipher> BlockMode<C> for Cbc<C> {
+    type IvSize = C::BlockSize;
+
+    fn new(cipher: C, iv: &Block<C>) -> Self {
+        Cbc { cipher, iv: iv.clone() }
+    }
+
+    fn encrypt_blocks(&mut self, blocks: &mut [Block<C>]) {
+        for block in blocks {
+            xor(block, &self.iv);
+            self.cipher.encrypt_block(block);
+            self.iv = block.clone();
+        }
+    }
+
+    fn decrypt_blocks(&mut self, blocks: &mut [Block<C>]) {
+        let pb = C::ParBlocks::to_usize();
+        for chunk in blocks.chunks_mut(pb) {
+            if chunk.len() == pb {
+                let par_blocks: &mut ParBlocks<C> =
+                    GenericArray::from_mut_slice(chunk);
+                let ciphertext = par_blocks.clone();
+                self.cipher.decrypt_blocks(par_blocks);
+                xor(&mut par_blocks[0], &self.iv);
+                for i in 1..pb {
+                    xor(&mut par_blocks[i], &ciphertext[i - 1]);
+                }
+                self.iv = ciphertext[pb - 1].clone();
+            } else {
+                for block in chunk {
+                    let ciphertext = block.clone();
+                    self.cipher.decrypt_block(block);
+                    xor(block, &self.iv);
+                    self.iv = ciphertext;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::Unsigned;
+use stream_cipher::{AsyncStreamCipher, NewFixStreamCipher};
+use utils::{Block, ParBlocks, xor};
+
+/// [Cipher Feedback][1] (CFB) mode instance with full block feedback.
+///
+/// Encryption is sequential, while decryption processes `ParBlocks`
+/// blocks at once.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#CFB
+pub struct Cfb<C: BlockEncrypt> {
+    cipher: C,
+    /// Keystream block which gets replaced by ciphertext as it's produced
+    iv: Block<C>,
+    pos: usize,
+}
+
+impl<C: BlockEncrypt> Cfb<C> {
+    /// Create new CFB mode instance from initialized block cipher and IV.
+    pub fn from_cipher(cipher: C, iv: &Block<C>) -> Self {
+        Cfb { cipher, iv: iv.clone(), pos: C::BlockSize::to_usize() }
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> NewFixStreamCipher for Cfb<C> {
+    type KeySize = C::KeySize;
+    type NonceSize = C::BlockSize;
+
+    fn new(key: &GenericArray<u8, C::KeySize>, iv: &Block<C>) -> Self {
+        Self::from_cipher(C::new(key), iv)
+    }
+}
+
+impl<C: BlockEncrypt> AsyncStreamCipher for Cfb<C> {
+    fn encrypt(&mut self, data: &mut [u8]) {
+        let bs = C::BlockSize::to_usize();
+        let mut i = 0;
+        while i < data.len() {
+            if self.pos == bs {
+                self.cipher.encrypt_block(&mut self.iv);
+                self.pos = 0;
+            }
+            self.iv[self.pos] ^= data[i];
+            data[i] = self.iv[self.pos];
+            self.pos += 1;
+            i += 1;
+        }
+    }
+
+    fn decrypt(&mut self, data: &mut [u8]) {
+        let bs = C::BlockSize::to_usize();
+        let pb = C::ParBlocks::to_usize();
+        let mut i = 0;
+        while i < data.len() {
+            if self.pos == bs && pb > 1 && data.len() - i >= pb * bs {
+                // keystream blocks depend only on the known ciphertext
+                let chunk = &mut data[i..i + pb * bs];
+                let mut blocks = ParBlocks::<C>::default();
+                blocks[0] = self.iv.clone();
+                for (block, ct) in blocks[1..].iter_mut().zip(chunk.chunks(bs)) {
+                    block.copy_from_slice(ct);
+                }
+                self.iv.copy_from_slice(&chunk[(pb - 1) * bs..]);
+                self.cipher.encrypt_blocks(&mut blocks);
+                for (ct, block) in chunk.chunks_mut(bs).zip(blocks.iter()) {
+                    xor(ct, block);
+                }
+                i += pb * bs;
+                continue;
+            }
+            if self.pos == bs {
+                self.cipher.encrypt_block(&mut self.iv);
+                self.pos = 0;
+            }
+            let ct = data[i];
+            data[i] ^= self.iv[self.pos];
+            self.iv[self.pos] = ct;
+            self.pos += 1;
+            i += 1;
+        }
+    }
+}
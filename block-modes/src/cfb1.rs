@@ -0,0 +1,72 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use stream_cipher::{AsyncStreamCipher, NewFixStreamCipher};
+use utils::Block;
+
+/// [Cipher Feedback][1] (CFB) mode instance with 1-bit feedback.
+///
+/// Every bit requires a block cipher invocation, bits are processed starting
+/// from the most significant bit of every byte.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#CFB
+pub struct Cfb1<C: BlockEncrypt> {
+    cipher: C,
+    /// Shift register with the last block-size bits of ciphertext
+    iv: Block<C>,
+}
+
+impl<C: BlockEncrypt> Cfb1<C> {
+    /// Create new CFB1 mode instance from initialized block cipher and IV.
+    pub fn from_cipher(cipher: C, iv: &Block<C>) -> Self {
+        Cfb1 { cipher, iv: iv.clone() }
+    }
+
+    fn keystream_bit(&self) -> u8 {
+        let mut block = self.iv.clone();
+        self.cipher.encrypt_block(&mut block);
+        block[0] >> 7
+    }
+
+    fn shift(&mut self, bit: u8) {
+        let bs = self.iv.len();
+        for j in 0..bs - 1 {
+            self.iv[j] = (self.iv[j] << 1) | (self.iv[j + 1] >> 7);
+        }
+        self.iv[bs - 1] = (self.iv[bs - 1] << 1) | bit;
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> NewFixStreamCipher for Cfb1<C> {
+    type KeySize = C::KeySize;
+    type NonceSize = C::BlockSize;
+
+    fn new(key: &GenericArray<u8, C::KeySize>, iv: &Block<C>) -> Self {
+        Self::from_cipher(C::new(key), iv)
+    }
+}
+
+impl<C: BlockEncrypt> AsyncStreamCipher for Cfb1<C> {
+    fn encrypt(&mut self, data: &mut [u8]) {
+        for b in data.iter_mut() {
+            let mut out = 0u8;
+            for j in (0..8).rev() {
+                let ct = ((*b >> j) & 1) ^ self.keystream_bit();
+                self.shift(ct);
+                out |= ct << j;
+            }
+            *b = out;
+        }
+    }
+
+    fn decrypt(&mut self, data: &mut [u8]) {
+        for b in data.iter_mut() {
+            let mut out = 0u8;
+            for j in (0..8).rev() {
+                let ct = (*b >> j) & 1;
+                out |= (ct ^ self.keystream_bit()) << j;
+                self.shift(ct);
+            }
+            *b = out;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::Unsigned;
+use stream_cipher::{AsyncStreamCipher, NewFixStreamCipher};
+use utils::{Block, ParBlocks};
+
+/// [Cipher Feedback][1] (CFB) mode instance with 8-bit feedback.
+///
+/// Every byte requires a block cipher invocation. Encryption is sequential,
+/// while decryption processes `ParBlocks` bytes at once.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#CFB
+pub struct Cfb8<C: BlockEncrypt> {
+    cipher: C,
+    /// Shift register with the last block-size bytes of ciphertext
+    iv: Block<C>,
+}
+
+impl<C: BlockEncrypt> Cfb8<C> {
+    /// Create new CFB8 mode instance from initialized block cipher and IV.
+    pub fn from_cipher(cipher: C, iv: &Block<C>) -> Self {
+        Cfb8 { cipher, iv: iv.clone() }
+    }
+
+    fn shift(&mut self, ct: u8) {
+        let bs = self.iv.len();
+        for j in 0..bs - 1 {
+            self.iv[j] = self.iv[j + 1];
+        }
+        self.iv[bs - 1] = ct;
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> NewFixStreamCipher for Cfb8<C> {
+    type KeySize = C::KeySize;
+    type NonceSize = C::BlockSize;
+
+    fn new(key: &GenericArray<u8, C::KeySize>, iv: &Block<C>) -> Self {
+        Self::from_cipher(C::new(key), iv)
+    }
+}
+
+impl<C: BlockEncrypt> AsyncStreamCipher for Cfb8<C> {
+    fn encrypt(&mut self, data: &mut [u8]) {
+        for b in data.iter_mut() {
+            let mut block = self.iv.clone();
+            self.cipher.encrypt_block(&mut block);
+            *b ^= block[0];
+            self.shift(*b);
+        }
+    }
+
+    fn decrypt(&mut self, data: &mut [u8]) {
+        let bs = C::BlockSize::to_usize();
+        let pb = C::ParBlocks::to_usize();
+        let mut i = 0;
+        if pb > 1 && pb <= bs {
+            while data.len() - i >= pb {
+                // shift register for every byte is determined by the
+                // previous ciphertext bytes
+                let mut blocks = ParBlocks::<C>::default();
+                for (k, block) in blocks.iter_mut().enumerate() {
+                    block[..bs - k].copy_from_slice(&self.iv[k..]);
+                    block[bs - k..].copy_from_slice(&data[i..i + k]);
+                }
+                self.cipher.encrypt_blocks(&mut blocks);
+                for (k, block) in blocks.iter().enumerate() {
+                    let ct = data[i + k];
+                    data[i + k] ^= block[0];
+                    self.shift(ct);
+                }
+                i += pb;
+            }
+        }
+        for b in data[i..].iter_mut() {
+            let mut block = self.iv.clone();
+            self.cipher.encrypt_block(&mut block);
+            let ct = *b;
+            *b ^= block[0];
+            self.shift(ct);
+        }
+    }
+}
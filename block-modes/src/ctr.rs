@@ -0,0 +1,247 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::{GenericArray, ArrayLength};
+use block_cipher_trait::generic_array::typenum::Unsigned;
+use stream_cipher::{
+    LoopError, NewFixStreamCipher, StreamCipherCore, StreamCipherSeek,
+};
+use core::cmp;
+use core::marker::PhantomData;
+use utils::{Block, ParBlocks, xor, xor_b2b};
+
+/// Trait which defines how counter is stored inside the counter block.
+pub trait CtrFlavor {
+    /// Return number of counter blocks which can be generated starting from
+    /// the initial counter block `nonce` before counter wraps. Result is
+    /// saturated at `u64::MAX`.
+    fn blocks_left<N: ArrayLength<u8>>(nonce: &GenericArray<u8, N>) -> u64;
+
+    /// Write counter block with index `n` into `block`.
+    ///
+    /// `n` must be smaller than the value returned by `blocks_left`.
+    fn counter_block<N: ArrayLength<u8>>(
+        nonce: &GenericArray<u8, N>, n: u64, block: &mut GenericArray<u8, N>,
+    );
+}
+
+/// Whole counter block is treated as a big endian integer, for 16 byte
+/// blocks it's a 128-bit counter.
+pub enum Ctr128BE {}
+
+/// Last 4 bytes of the counter block are treated as a big endian 32-bit
+/// counter, as used by GCM. Block size must be at least 4 bytes.
+pub enum Ctr32BE {}
+
+/// First 8 bytes of the counter block are treated as a little endian 64-bit
+/// counter. Block size must be at least 8 bytes.
+pub enum Ctr64LE {}
+
+impl CtrFlavor for Ctr128BE {
+    fn blocks_left<N: ArrayLength<u8>>(nonce: &GenericArray<u8, N>) -> u64 {
+        let n = nonce.len();
+        let k = cmp::min(n, 8);
+        if nonce[..n - k].iter().any(|&b| b != 0xff) {
+            return u64::max_value();
+        }
+        let mut low = 0u64;
+        for &b in nonce[n - k..].iter() {
+            low = (low << 8) | b as u64;
+        }
+        if k == 8 {
+            // 2^64 - low, saturated
+            if low == 0 { u64::max_value() } else { low.wrapping_neg() }
+        } else {
+            (1u64 << (8 * k)) - low
+        }
+    }
+
+    fn counter_block<N: ArrayLength<u8>>(
+        nonce: &GenericArray<u8, N>, n: u64, block: &mut GenericArray<u8, N>,
+    ) {
+        let mut carry = n;
+        for (b, &v) in block.iter_mut().rev().zip(nonce.iter().rev()) {
+            let sum = v as u64 + (carry & 0xff);
+            *b = sum as u8;
+            carry = (carry >> 8) + (sum >> 8);
+        }
+    }
+}
+
+impl CtrFlavor for Ctr32BE {
+    fn blocks_left<N: ArrayLength<u8>>(nonce: &GenericArray<u8, N>) -> u64 {
+        let n = nonce.len();
+        let c = read_u32_be(&nonce[n - 4..]);
+        (1u64 << 32) - c as u64
+    }
+
+    fn counter_block<N: ArrayLength<u8>>(
+        nonce: &GenericArray<u8, N>, n: u64, block: &mut GenericArray<u8, N>,
+    ) {
+        let len = nonce.len();
+        let c = read_u32_be(&nonce[len - 4..]).wrapping_add(n as u32);
+        block.copy_from_slice(nonce);
+        for (i, b) in block[len - 4..].iter_mut().enumerate() {
+            *b = (c >> (24 - 8 * i)) as u8;
+        }
+    }
+}
+
+impl CtrFlavor for Ctr64LE {
+    fn blocks_left<N: ArrayLength<u8>>(nonce: &GenericArray<u8, N>) -> u64 {
+        let c = read_u64_le(&nonce[..8]);
+        if c == 0 { u64::max_value() } else { c.wrapping_neg() }
+    }
+
+    fn counter_block<N: ArrayLength<u8>>(
+        nonce: &GenericArray<u8, N>, n: u64, block: &mut GenericArray<u8, N>,
+    ) {
+        let c = read_u64_le(&nonce[..8]).wrapping_add(n);
+        block.copy_from_slice(nonce);
+        for (i, b) in block[..8].iter_mut().enumerate() {
+            *b = (c >> (8 * i)) as u8;
+        }
+    }
+}
+
+#[inline(always)]
+fn read_u32_be(buf: &[u8]) -> u32 {
+    buf.iter().fold(0, |acc, &b| (acc << 8) | b as u32)
+}
+
+#[inline(always)]
+fn read_u64_le(buf: &[u8]) -> u64 {
+    buf.iter().rev().fold(0, |acc, &b| (acc << 8) | b as u64)
+}
+
+/// [Counter][1] (CTR) mode instance generic over block cipher and
+/// counter flavor.
+///
+/// Keystream is generated using `encrypt_blocks` for `ParBlocks` blocks
+/// at once.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#CTR
+pub struct Ctr<C: BlockEncrypt, F: CtrFlavor = Ctr128BE> {
+    cipher: C,
+    nonce: Block<C>,
+    /// Number of keystream blocks available before counter wraps
+    blocks_left: u64,
+    /// Current position in the keystream in bytes
+    pos: u64,
+    /// Cached keystream block with index `cache_idx`
+    cache: Block<C>,
+    cache_idx: Option<u64>,
+    _flavor: PhantomData<F>,
+}
+
+impl<C: BlockEncrypt, F: CtrFlavor> Ctr<C, F> {
+    /// Create new CTR mode instance from initialized block cipher and
+    /// initial counter block.
+    pub fn from_cipher(cipher: C, nonce: &Block<C>) -> Self {
+        Ctr {
+            cipher,
+            nonce: nonce.clone(),
+            blocks_left: F::blocks_left(nonce),
+            pos: 0,
+            cache: Default::default(),
+            cache_idx: None,
+            _flavor: PhantomData,
+        }
+    }
+
+    fn check_data_len(&self, len: usize) -> Result<(), LoopError> {
+        if len == 0 {
+            return Ok(());
+        }
+        let bs = C::BlockSize::to_u64();
+        let end = self.pos.checked_add(len as u64).ok_or(LoopError)?;
+        if (end - 1) / bs >= self.blocks_left {
+            Err(LoopError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Generate `len` bytes of keystream starting from the current position
+    /// and pass it to `f` in chunks together with their offsets.
+    fn keystream<G: FnMut(usize, &[u8])>(&mut self, len: usize, mut f: G) {
+        let bs = C::BlockSize::to_usize();
+        let pb = C::ParBlocks::to_usize();
+
+        let mut i = 0;
+        while i < len {
+            let idx = self.pos / bs as u64;
+            let offset = (self.pos % bs as u64) as usize;
+            let rem = len - i;
+
+            if pb > 1 && offset == 0 && rem >= pb * bs {
+                let mut blocks = ParBlocks::<C>::default();
+                for (j, block) in blocks.iter_mut().enumerate() {
+                    F::counter_block(&self.nonce, idx + j as u64, block);
+                }
+                self.cipher.encrypt_blocks(&mut blocks);
+                for block in blocks.iter() {
+                    f(i, block);
+                    i += bs;
+                }
+                self.pos += (pb * bs) as u64;
+                continue;
+            }
+
+            if self.cache_idx != Some(idx) {
+                F::counter_block(&self.nonce, idx, &mut self.cache);
+                self.cipher.encrypt_block(&mut self.cache);
+                self.cache_idx = Some(idx);
+            }
+            let n = cmp::min(bs - offset, rem);
+            f(i, &self.cache[offset..offset + n]);
+            self.pos += n as u64;
+            i += n;
+        }
+    }
+}
+
+impl<C, F> NewFixStreamCipher for Ctr<C, F>
+    where C: NewBlockCipher + BlockEncrypt, F: CtrFlavor
+{
+    type KeySize = C::KeySize;
+    type NonceSize = C::BlockSize;
+
+    fn new(
+        key: &GenericArray<u8, C::KeySize>, nonce: &Block<C>,
+    ) -> Self {
+        Self::from_cipher(C::new(key), nonce)
+    }
+}
+
+impl<C: BlockEncrypt, F: CtrFlavor> StreamCipherCore for Ctr<C, F> {
+    fn try_apply_keystream(&mut self, data: &mut [u8])
+        -> Result<(), LoopError>
+    {
+        self.check_data_len(data.len())?;
+        self.keystream(data.len(), |i, ks| {
+            xor(&mut data[i..i + ks.len()], ks);
+        });
+        Ok(())
+    }
+
+    fn try_apply_keystream_b2b(&mut self, input: &[u8], output: &mut [u8])
+        -> Result<(), LoopError>
+    {
+        assert_eq!(input.len(), output.len());
+        self.check_data_len(input.len())?;
+        self.keystream(input.len(), |i, ks| {
+            let n = ks.len();
+            xor_b2b(&input[i..i + n], ks, &mut output[i..i + n]);
+        });
+        Ok(())
+    }
+}
+
+impl<C: BlockEncrypt, F: CtrFlavor> StreamCipherSeek for Ctr<C, F> {
+    fn current_pos(&self) -> u64 {
+        self.pos
+    }
+
+    fn seek(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
@@ -0,0 +1,930 @@
+//! Test vectors and helpers for testing block modes with concrete ciphers.
+use block_cipher_trait::{
+    BlockCipher, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::{U8, U16, Unsigned};
+use stream_cipher::{
+    AsyncStreamCipher, NewFixStreamCipher, StreamCipherCore, StreamCipherSeek,
+};
+use super::{BlockMode, KeyWrapError, Kw, Kwp, Xts};
+use super::padding::{self, Padding, Pkcs7};
+
+/// Block mode test vector
+pub struct Test {
+    pub key: &'static [u8],
+    pub iv: &'static [u8],
+    pub plaintext: &'static [u8],
+    pub ciphertext: &'static [u8],
+}
+
+const SP800_38A_KEY: &[u8] = &[
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6,
+    0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+
+const SP800_38A_IV: &[u8] = &[
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+    0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+];
+
+const SP800_38A_PLAINTEXT: &[u8] = &[
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96,
+    0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c,
+    0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+    0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11,
+    0xe5, 0xfb, 0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef,
+    0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17,
+    0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10,
+];
+
+/// ECB-AES128 test vector from NIST SP 800-38A, F.1.1
+pub const ECB_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: &[],
+    plaintext: SP800_38A_PLAINTEXT,
+    ciphertext: &[
+        0x3a, 0xd7, 0x7b, 0xb4, 0x0d, 0x7a, 0x36, 0x60,
+        0xa8, 0x9e, 0xca, 0xf3, 0x24, 0x66, 0xef, 0x97,
+        0xf5, 0xd3, 0xd5, 0x85, 0x03, 0xb9, 0x69, 0x9d,
+        0xe7, 0x85, 0x89, 0x5a, 0x96, 0xfd, 0xba, 0xaf,
+        0x43, 0xb1, 0xcd, 0x7f, 0x59, 0x8e, 0xce, 0x23,
+        0x88, 0x1b, 0x00, 0xe3, 0xed, 0x03, 0x06, 0x88,
+        0x7b, 0x0c, 0x78, 0x5e, 0x27, 0xe8, 0xad, 0x3f,
+        0x82, 0x23, 0x20, 0x71, 0x04, 0x72, 0x5d, 0xd4,
+    ],
+};
+
+/// CBC-AES128 test vector from NIST SP 800-38A, F.2.1
+pub const CBC_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: SP800_38A_IV,
+    plaintext: SP800_38A_PLAINTEXT,
+    ciphertext: &[
+        0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46,
+        0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19, 0x7d,
+        0x50, 0x86, 0xcb, 0x9b, 0x50, 0x72, 0x19, 0xee,
+        0x95, 0xdb, 0x11, 0x3a, 0x91, 0x76, 0x78, 0xb2,
+        0x73, 0xbe, 0xd6, 0xb8, 0xe3, 0xc1, 0x74, 0x3b,
+        0x71, 0x16, 0xe6, 0x9e, 0x22, 0x22, 0x95, 0x16,
+        0x3f, 0xf1, 0xca, 0xa1, 0x68, 0x1f, 0xac, 0x09,
+        0x12, 0x0e, 0xca, 0x30, 0x75, 0x86, 0xe1, 0xa7,
+    ],
+};
+
+/// PCBC-AES128 test vector which uses key, IV and plaintext from
+/// NIST SP 800-38A
+pub const PCBC_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: SP800_38A_IV,
+    plaintext: SP800_38A_PLAINTEXT,
+    ciphertext: &[
+        0x76, 0x49, 0xab, 0xac, 0x81, 0x19, 0xb2, 0x46,
+        0xce, 0xe9, 0x8e, 0x9b, 0x12, 0xe9, 0x19, 0x7d,
+        0x9e, 0x8b, 0xaf, 0xf1, 0x2a, 0xd5, 0x27, 0x0a,
+        0x0d, 0x1e, 0xef, 0x93, 0xd7, 0x03, 0x79, 0x94,
+        0x57, 0x00, 0xb3, 0x98, 0x03, 0x77, 0x9f, 0xa3,
+        0x5a, 0x3c, 0x60, 0x0a, 0x49, 0xa1, 0x63, 0xc0,
+        0x33, 0xae, 0x19, 0x9f, 0x27, 0x37, 0x9f, 0x21,
+        0xbe, 0x6d, 0xd5, 0x7d, 0x29, 0x5c, 0xc8, 0x7d,
+    ],
+};
+
+/// CTR-AES128 test vector from NIST SP 800-38A, F.5.1
+pub const CTR_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: &[
+        0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+        0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+    ],
+    plaintext: SP800_38A_PLAINTEXT,
+    ciphertext: &[
+        0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26,
+        0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+        0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff,
+        0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff, 0xfd, 0xff,
+        0x5a, 0xe4, 0xdf, 0x3e, 0xdb, 0xd5, 0xd3, 0x5e,
+        0x5b, 0x4f, 0x09, 0x02, 0x0d, 0xb0, 0x3e, 0xab,
+        0x1e, 0x03, 0x1d, 0xda, 0x2f, 0xbe, 0x03, 0xd1,
+        0x79, 0x21, 0x70, 0xa0, 0xf3, 0x00, 0x9c, 0xee,
+    ],
+};
+
+/// CFB128-AES128 test vector from NIST SP 800-38A, F.3.13
+pub const CFB_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: SP800_38A_IV,
+    plaintext: SP800_38A_PLAINTEXT,
+    ciphertext: &[
+        0x3b, 0x3f, 0xd9, 0x2e, 0xb7, 0x2d, 0xad, 0x20,
+        0x33, 0x34, 0x49, 0xf8, 0xe8, 0x3c, 0xfb, 0x4a,
+        0xc8, 0xa6, 0x45, 0x37, 0xa0, 0xb3, 0xa9, 0x3f,
+        0xcd, 0xe3, 0xcd, 0xad, 0x9f, 0x1c, 0xe5, 0x8b,
+        0x26, 0x75, 0x1f, 0x67, 0xa3, 0xcb, 0xb1, 0x40,
+        0xb1, 0x80, 0x8c, 0xf1, 0x87, 0xa4, 0xf4, 0xdf,
+        0xc0, 0x4b, 0x05, 0x35, 0x7c, 0x5d, 0x1c, 0x0e,
+        0xea, 0xc4, 0xc6, 0x6f, 0x9f, 0xf7, 0xf2, 0xe6,
+    ],
+};
+
+/// CFB8-AES128 test vector from NIST SP 800-38A, F.3.7
+pub const CFB8_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: SP800_38A_IV,
+    plaintext: &[
+        0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96,
+        0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+        0xae, 0x2d,
+    ],
+    ciphertext: &[
+        0x3b, 0x79, 0x42, 0x4c, 0x9c, 0x0d, 0xd4, 0x36,
+        0xba, 0xce, 0x9e, 0x0e, 0xd4, 0x58, 0x6a, 0x4f,
+        0x32, 0xb9,
+    ],
+};
+
+/// CFB1-AES128 test vector from NIST SP 800-38A, F.3.1
+pub const CFB1_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: SP800_38A_IV,
+    plaintext: &[0x6b, 0xc1],
+    ciphertext: &[0x68, 0xb3],
+};
+
+/// OFB-AES128 test vector from NIST SP 800-38A, F.4.1
+pub const OFB_AES128: Test = Test {
+    key: SP800_38A_KEY,
+    iv: SP800_38A_IV,
+    plaintext: SP800_38A_PLAINTEXT,
+    ciphertext: &[
+        0x3b, 0x3f, 0xd9, 0x2e, 0xb7, 0x2d, 0xad, 0x20,
+        0x33, 0x34, 0x49, 0xf8, 0xe8, 0x3c, 0xfb, 0x4a,
+        0x77, 0x89, 0x50, 0x8d, 0x16, 0x91, 0x8f, 0x03,
+        0xf5, 0x3c, 0x52, 0xda, 0xc5, 0x4e, 0xd8, 0x25,
+        0x97, 0x40, 0x05, 0x1e, 0x9c, 0x5f, 0xec, 0xf6,
+        0x43, 0x44, 0xf7, 0xa8, 0x22, 0x60, 0xed, 0xcc,
+        0x30, 0x4c, 0x65, 0x28, 0xf6, 0x59, 0xc7, 0x78,
+        0x66, 0xa5, 0x10, 0xd9, 0xc1, 0xd6, 0xae, 0x5e,
+    ],
+};
+
+/// XTS-AES-128 test vectors from IEEE 1619-2007, Annex B (vectors 1, 2 and
+/// 15-18). Key is a concatenation of data and tweak keys, IV is a tweak.
+pub const XTS_AES128: &[Test] = &[
+    Test {
+        key: &[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        iv: &[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        plaintext: &[
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        ciphertext: &[
+            0x91, 0x7c, 0xf6, 0x9e, 0xbd, 0x68, 0xb2, 0xec,
+            0x9b, 0x9f, 0xe9, 0xa3, 0xea, 0xdd, 0xa6, 0x92,
+            0xcd, 0x43, 0xd2, 0xf5, 0x95, 0x98, 0xed, 0x85,
+            0x8c, 0x02, 0xc2, 0x65, 0x2f, 0xbf, 0x92, 0x2e,
+        ],
+    },
+    Test {
+        key: &[
+            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+            0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+            0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
+            0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
+        ],
+        iv: &[
+            0x33, 0x33, 0x33, 0x33, 0x33, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        plaintext: &[
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+            0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x44,
+        ],
+        ciphertext: &[
+            0xc4, 0x54, 0x18, 0x5e, 0x6a, 0x16, 0x93, 0x6e,
+            0x39, 0x33, 0x40, 0x38, 0xac, 0xef, 0x83, 0x8b,
+            0xfb, 0x18, 0x6f, 0xff, 0x74, 0x80, 0xad, 0xc4,
+            0x28, 0x93, 0x82, 0xec, 0xd6, 0xd3, 0x94, 0xf0,
+        ],
+    },
+    Test {
+        key: &[
+            0xff, 0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8,
+            0xf7, 0xf6, 0xf5, 0xf4, 0xf3, 0xf2, 0xf1, 0xf0,
+            0xbf, 0xbe, 0xbd, 0xbc, 0xbb, 0xba, 0xb9, 0xb8,
+            0xb7, 0xb6, 0xb5, 0xb4, 0xb3, 0xb2, 0xb1, 0xb0,
+        ],
+        iv: &[
+            0x9a, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        plaintext: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10,
+        ],
+        ciphertext: &[
+            0x6c, 0x16, 0x25, 0xdb, 0x46, 0x71, 0x52, 0x2d,
+            0x3d, 0x75, 0x99, 0x60, 0x1d, 0xe7, 0xca, 0x09,
+            0xed,
+        ],
+    },
+    Test {
+        key: &[
+            0xff, 0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8,
+            0xf7, 0xf6, 0xf5, 0xf4, 0xf3, 0xf2, 0xf1, 0xf0,
+            0xbf, 0xbe, 0xbd, 0xbc, 0xbb, 0xba, 0xb9, 0xb8,
+            0xb7, 0xb6, 0xb5, 0xb4, 0xb3, 0xb2, 0xb1, 0xb0,
+        ],
+        iv: &[
+            0x9a, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        plaintext: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11,
+        ],
+        ciphertext: &[
+            0xd0, 0x69, 0x44, 0x4b, 0x7a, 0x7e, 0x0c, 0xab,
+            0x09, 0xe2, 0x44, 0x47, 0xd2, 0x4d, 0xeb, 0x1f,
+            0xed, 0xbf,
+        ],
+    },
+    Test {
+        key: &[
+            0xff, 0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8,
+            0xf7, 0xf6, 0xf5, 0xf4, 0xf3, 0xf2, 0xf1, 0xf0,
+            0xbf, 0xbe, 0xbd, 0xbc, 0xbb, 0xba, 0xb9, 0xb8,
+            0xb7, 0xb6, 0xb5, 0xb4, 0xb3, 0xb2, 0xb1, 0xb0,
+        ],
+        iv: &[
+            0x9a, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        plaintext: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12,
+        ],
+        ciphertext: &[
+            0xe5, 0xdf, 0x13, 0x51, 0xc0, 0x54, 0x4b, 0xa1,
+            0x35, 0x0b, 0x33, 0x63, 0xcd, 0x8e, 0xf4, 0xbe,
+            0xed, 0xbf, 0x9d,
+        ],
+    },
+    Test {
+        key: &[
+            0xff, 0xfe, 0xfd, 0xfc, 0xfb, 0xfa, 0xf9, 0xf8,
+            0xf7, 0xf6, 0xf5, 0xf4, 0xf3, 0xf2, 0xf1, 0xf0,
+            0xbf, 0xbe, 0xbd, 0xbc, 0xbb, 0xba, 0xb9, 0xb8,
+            0xb7, 0xb6, 0xb5, 0xb4, 0xb3, 0xb2, 0xb1, 0xb0,
+        ],
+        iv: &[
+            0x9a, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+        plaintext: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13,
+        ],
+        ciphertext: &[
+            0x9d, 0x84, 0xc8, 0x13, 0xf7, 0x19, 0xaa, 0x2c,
+            0x7b, 0xe3, 0xf6, 0x61, 0x71, 0xc7, 0xc5, 0xc2,
+            0xed, 0xbf, 0x9d, 0xac,
+        ],
+    },
+];
+
+/// KW-AES128 test vector from RFC 3394, section 4.1
+pub const KW_AES128: &[Test] = &[
+    Test {
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        ],
+        iv: &[],
+        plaintext: &[
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+        ],
+        ciphertext: &[
+            0x1f, 0xa6, 0x8b, 0x0a, 0x81, 0x12, 0xb4, 0x47,
+            0xae, 0xf3, 0x4b, 0xd8, 0xfb, 0x5a, 0x7b, 0x82,
+            0x9d, 0x3e, 0x86, 0x23, 0x71, 0xd2, 0xcf, 0xe5,
+        ],
+    },
+];
+
+/// KW-AES256 test vectors from RFC 3394, sections 4.3 and 4.6
+pub const KW_AES256: &[Test] = &[
+    Test {
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        iv: &[],
+        plaintext: &[
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+        ],
+        ciphertext: &[
+            0x64, 0xe8, 0xc3, 0xf9, 0xce, 0x0f, 0x5b, 0xa2,
+            0x63, 0xe9, 0x77, 0x79, 0x05, 0x81, 0x8a, 0x2a,
+            0x93, 0xc8, 0x19, 0x1e, 0x7d, 0x6e, 0x8a, 0xe7,
+        ],
+    },
+    Test {
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        iv: &[],
+        plaintext: &[
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77,
+            0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff,
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        ],
+        ciphertext: &[
+            0x28, 0xc9, 0xf4, 0x04, 0xc4, 0xb8, 0x10, 0xf4,
+            0xcb, 0xcc, 0xb3, 0x5c, 0xfb, 0x87, 0xf8, 0x26,
+            0x3f, 0x57, 0x86, 0xe2, 0xd8, 0x0e, 0xd3, 0x26,
+            0xcb, 0xc7, 0xf0, 0xe7, 0x1a, 0x99, 0xf4, 0x3b,
+            0xfb, 0x98, 0x8b, 0x9b, 0x7a, 0x02, 0xdd, 0x21,
+        ],
+    },
+];
+
+/// KWP-AES192 test vectors from RFC 5649, section 6
+pub const KWP_AES192: &[Test] = &[
+    Test {
+        key: &[
+            0x58, 0x40, 0xdf, 0x6e, 0x29, 0xb0, 0x2a, 0xf1,
+            0xab, 0x49, 0x3b, 0x70, 0x5b, 0xf1, 0x6e, 0xa1,
+            0xae, 0x83, 0x38, 0xf4, 0xdc, 0xc1, 0x76, 0xa8,
+        ],
+        iv: &[],
+        plaintext: &[
+            0xc3, 0x7b, 0x7e, 0x64, 0x92, 0x58, 0x43, 0x40,
+            0xbe, 0xd1, 0x22, 0x07, 0x80, 0x89, 0x41, 0x15,
+            0x50, 0x68, 0xf7, 0x38,
+        ],
+        ciphertext: &[
+            0x13, 0x8b, 0xde, 0xaa, 0x9b, 0x8f, 0xa7, 0xfc,
+            0x61, 0xf9, 0x77, 0x42, 0xe7, 0x22, 0x48, 0xee,
+            0x5a, 0xe6, 0xae, 0x53, 0x60, 0xd1, 0xae, 0x6a,
+            0x5f, 0x54, 0xf3, 0x73, 0xfa, 0x54, 0x3b, 0x6a,
+        ],
+    },
+    Test {
+        key: &[
+            0x58, 0x40, 0xdf, 0x6e, 0x29, 0xb0, 0x2a, 0xf1,
+            0xab, 0x49, 0x3b, 0x70, 0x5b, 0xf1, 0x6e, 0xa1,
+            0xae, 0x83, 0x38, 0xf4, 0xdc, 0xc1, 0x76, 0xa8,
+        ],
+        iv: &[],
+        plaintext: &[
+            0x46, 0x6f, 0x72, 0x50, 0x61, 0x73, 0x69,
+        ],
+        ciphertext: &[
+            0xaf, 0xbe, 0xb0, 0xf0, 0x7d, 0xfb, 0xf5, 0x41,
+            0x92, 0x00, 0xf2, 0xcc, 0xb5, 0x0b, 0xb2, 0x4f,
+        ],
+    },
+];
+
+/// Padding test vector for 8 byte blocks
+pub struct PaddingTest {
+    /// Padding expected for messages of 0, 7, 8 and 9 bytes
+    pub padding: [&'static [u8]; 4],
+    /// Last blocks with malformed padding
+    pub malformed: &'static [[u8; 8]],
+}
+
+/// PKCS#7 padding test vector
+pub const PKCS7_PADDING: PaddingTest = PaddingTest {
+    padding: [&[8; 8], &[1], &[8; 8], &[7; 7]],
+    malformed: &[
+        [1, 2, 3, 4, 5, 6, 7, 0],
+        [9, 9, 9, 9, 9, 9, 9, 9],
+        [1, 2, 3, 4, 5, 3, 2, 3],
+        [7, 8, 8, 8, 8, 8, 8, 8],
+    ],
+};
+
+/// ANSI X.923 padding test vector
+pub const ANSI_X923_PADDING: PaddingTest = PaddingTest {
+    padding: [
+        &[0, 0, 0, 0, 0, 0, 0, 8],
+        &[1],
+        &[0, 0, 0, 0, 0, 0, 0, 8],
+        &[0, 0, 0, 0, 0, 0, 7],
+    ],
+    malformed: &[
+        [1, 2, 3, 4, 5, 6, 7, 0],
+        [0, 0, 0, 0, 0, 0, 0, 9],
+        [1, 2, 3, 4, 5, 1, 0, 3],
+        [1, 0, 0, 0, 0, 0, 0, 8],
+    ],
+};
+
+/// ISO/IEC 7816-4 padding test vector
+pub const ISO_7816_PADDING: PaddingTest = PaddingTest {
+    padding: [
+        &[0x80, 0, 0, 0, 0, 0, 0, 0],
+        &[0x80],
+        &[0x80, 0, 0, 0, 0, 0, 0, 0],
+        &[0x80, 0, 0, 0, 0, 0, 0],
+    ],
+    malformed: &[
+        [0, 0, 0, 0, 0, 0, 0, 0],
+        [1, 2, 3, 4, 5, 6, 7, 8],
+        [1, 2, 3, 0x80, 0, 0, 0, 1],
+        [0x80, 0x80, 0, 0, 0, 0, 0, 0x40],
+    ],
+};
+
+/// ISO 10126 padding test vector, filler bytes are zeros
+pub const ISO_10126_PADDING: PaddingTest = PaddingTest {
+    padding: [
+        &[0, 0, 0, 0, 0, 0, 0, 8],
+        &[1],
+        &[0, 0, 0, 0, 0, 0, 0, 8],
+        &[0, 0, 0, 0, 0, 0, 7],
+    ],
+    malformed: &[
+        [1, 2, 3, 4, 5, 6, 7, 0],
+        [1, 2, 3, 4, 5, 6, 7, 9],
+        [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    ],
+};
+
+/// Zero padding test vector
+pub const ZERO_PADDING: PaddingTest = PaddingTest {
+    padding: [&[], &[0], &[], &[0, 0, 0, 0, 0, 0, 0]],
+    malformed: &[],
+};
+
+/// Run block mode test using the provided test vector.
+///
+/// Message is processed both at once and split into parts of every
+/// possible number of blocks to check that chaining state is preserved
+/// between calls. Message prefixes of 0, `bs - 1`, `bs` and `bs + 1` bytes
+/// are encrypted and decrypted with PKCS#7 padding.
+pub fn run_test<C, M>(t: &Test)
+    where C: BlockCipher, M: BlockMode<C>
+{
+    let bs = C::BlockSize::to_usize();
+    let mut buf = [0u8; 1024];
+    let n = t.plaintext.len();
+    assert!(n <= buf.len(), "test vector is too long");
+
+    for blocks in 1..(n / bs + 1) {
+        let buf = &mut buf[..n];
+        buf.copy_from_slice(t.plaintext);
+        let mut mode = M::new_varkey(t.key, t.iv).unwrap();
+        for chunk in buf.chunks_mut(blocks * bs) {
+            mode.encrypt_nopad(chunk).unwrap();
+        }
+        assert_eq!(buf[..], t.ciphertext[..]);
+
+        let mut mode = M::new_varkey(t.key, t.iv).unwrap();
+        for chunk in buf.chunks_mut(blocks * bs) {
+            mode.decrypt_nopad(chunk).unwrap();
+        }
+        assert_eq!(buf[..], t.plaintext[..]);
+    }
+
+    // padded message prefixes, full blocks must match the test vector
+    for &m in [0, bs - 1, bs, bs + 1].iter() {
+        if m > n {
+            continue;
+        }
+        let msg = &t.plaintext[..m];
+        let full = m - m % bs;
+        let len = full + bs;
+        buf[..m].copy_from_slice(msg);
+        {
+            let mode = M::new_varkey(t.key, t.iv).unwrap();
+            let ct = mode.encrypt_pad::<Pkcs7>(&mut buf, m).unwrap();
+            assert_eq!(ct.len(), len);
+            assert_eq!(ct[..full], t.ciphertext[..full]);
+        }
+
+        #[cfg(feature = "std")]
+        {
+            let mode = M::new_varkey(t.key, t.iv).unwrap();
+            assert_eq!(mode.encrypt_vec::<Pkcs7>(msg)[..], buf[..len]);
+            let mode = M::new_varkey(t.key, t.iv).unwrap();
+            let res = mode.decrypt_vec::<Pkcs7>(&buf[..len]).unwrap();
+            assert_eq!(res[..], msg[..]);
+        }
+
+        let mode = M::new_varkey(t.key, t.iv).unwrap();
+        let res = mode.decrypt_pad::<Pkcs7>(&mut buf[..len]).unwrap();
+        assert_eq!(res, msg);
+    }
+}
+
+/// Run synchronous stream cipher mode test using the provided test vector.
+///
+/// Message is processed in chunks of every possible size, both in-place and
+/// buffer-to-buffer. Use this function for modes which do not implement
+/// `StreamCipherSeek`, e.g. OFB.
+pub fn run_stream_core_test<M>(t: &Test)
+    where M: NewFixStreamCipher + StreamCipherCore
+{
+    let mut buf = [0u8; 1024];
+    let n = t.plaintext.len();
+    assert!(n <= buf.len(), "test vector is too long");
+    let key = GenericArray::from_slice(t.key);
+    let iv = GenericArray::from_slice(t.iv);
+
+    for i in 1..(n + 1) {
+        let buf = &mut buf[..n];
+        buf.copy_from_slice(t.plaintext);
+        let mut mode = M::new(key, iv);
+        for chunk in buf.chunks_mut(i) {
+            mode.apply_keystream(chunk);
+        }
+        assert_eq!(buf[..], t.ciphertext[..]);
+
+        let mut mode = M::new(key, iv);
+        let chunks = t.ciphertext.chunks(i).zip(buf.chunks_mut(i));
+        for (input, output) in chunks {
+            mode.apply_keystream_b2b(input, output);
+        }
+        assert_eq!(buf[..], t.plaintext[..]);
+    }
+}
+
+/// Run stream cipher mode test using the provided test vector.
+///
+/// Message is processed in chunks of every possible size (both in-place
+/// and buffer-to-buffer), after that keystream is applied starting from
+/// every possible position using `StreamCipherSeek::seek`.
+pub fn run_stream_test<M>(t: &Test)
+    where M: NewFixStreamCipher + StreamCipherCore + StreamCipherSeek
+{
+    run_stream_core_test::<M>(t);
+
+    let mut buf = [0u8; 1024];
+    let n = t.plaintext.len();
+    assert!(n <= buf.len(), "test vector is too long");
+    let key = GenericArray::from_slice(t.key);
+    let iv = GenericArray::from_slice(t.iv);
+
+    for i in 1..(n + 1) {
+        let buf = &mut buf[..n];
+        buf.copy_from_slice(t.plaintext);
+        let mut mode = M::new(key, iv);
+        for chunk in buf.chunks_mut(i) {
+            mode.apply_keystream(chunk);
+        }
+        assert_eq!(buf[..], t.ciphertext[..]);
+        assert_eq!(mode.current_pos(), n as u64);
+    }
+
+    let mut mode = M::new(key, iv);
+    for i in 0..n {
+        let buf = &mut buf[i..n];
+        buf.copy_from_slice(&t.ciphertext[i..]);
+        mode.seek(i as u64);
+        mode.apply_keystream(buf);
+        assert_eq!(buf[..], t.plaintext[i..]);
+    }
+}
+
+/// Run asynchronous stream cipher mode test using the provided test vector.
+///
+/// Message is encrypted and decrypted in chunks of every possible size.
+pub fn run_async_test<M>(t: &Test)
+    where M: NewFixStreamCipher + AsyncStreamCipher
+{
+    let mut buf = [0u8; 1024];
+    let n = t.plaintext.len();
+    assert!(n <= buf.len(), "test vector is too long");
+    let key = GenericArray::from_slice(t.key);
+    let iv = GenericArray::from_slice(t.iv);
+
+    for i in 1..(n + 1) {
+        let buf = &mut buf[..n];
+        buf.copy_from_slice(t.plaintext);
+        let mut mode = M::new(key, iv);
+        for chunk in buf.chunks_mut(i) {
+            mode.encrypt(chunk);
+        }
+        assert_eq!(buf[..], t.ciphertext[..]);
+
+        let mut mode = M::new(key, iv);
+        for chunk in buf.chunks_mut(i) {
+            mode.decrypt(chunk);
+        }
+        assert_eq!(buf[..], t.plaintext[..]);
+    }
+}
+
+/// Run XTS test using the provided test vectors.
+///
+/// Sectors are encrypted and decrypted using `Xts::encrypt_sector` and
+/// `Xts::decrypt_sector`.
+pub fn run_xts_test<C>(tests: &[Test])
+    where C: NewBlockCipher + BlockEncrypt<BlockSize = U16> + BlockDecrypt
+{
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let n = t.plaintext.len();
+        assert!(n <= buf.len(), "test vector is too long");
+        let buf = &mut buf[..n];
+        let tweak = GenericArray::from_slice(t.iv);
+        let xts = Xts::<C>::new_varkey(t.key).unwrap();
+
+        buf.copy_from_slice(t.plaintext);
+        xts.encrypt_sector(buf, tweak).unwrap();
+        assert_eq!(buf[..], t.ciphertext[..]);
+
+        xts.decrypt_sector(buf, tweak).unwrap();
+        assert_eq!(buf[..], t.plaintext[..]);
+    }
+}
+
+/// Run KW test using the provided test vectors.
+///
+/// In addition to wrapping and unwrapping, checks that unwrapping of
+/// a modified wrapped key fails integrity check.
+pub fn run_kw_test<C>(tests: &[Test])
+    where C: NewBlockCipher + BlockEncrypt<BlockSize = U16> + BlockDecrypt
+{
+    let mut buf = [0u8; 1024];
+    let mut out = [0u8; 1024];
+    for t in tests.iter() {
+        let kw = Kw::<C>::new_varkey(t.key).unwrap();
+        assert_eq!(kw.wrap(t.plaintext, &mut buf).unwrap(), t.ciphertext);
+        assert_eq!(kw.unwrap(t.ciphertext, &mut out).unwrap(), t.plaintext);
+
+        let n = t.ciphertext.len();
+        buf[..n].copy_from_slice(t.ciphertext);
+        buf[n - 1] ^= 1;
+        let res = kw.unwrap(&buf[..n], &mut out);
+        assert_eq!(res, Err(KeyWrapError::IntegrityCheckFailed));
+    }
+}
+
+/// Run KWP test using the provided test vectors.
+///
+/// In addition to wrapping and unwrapping, checks that unwrapping of
+/// a modified wrapped key fails integrity check.
+pub fn run_kwp_test<C>(tests: &[Test])
+    where C: NewBlockCipher + BlockEncrypt<BlockSize = U16> + BlockDecrypt
+{
+    let mut buf = [0u8; 1024];
+    let mut out = [0u8; 1024];
+    for t in tests.iter() {
+        let kwp = Kwp::<C>::new_varkey(t.key).unwrap();
+        assert_eq!(kwp.wrap(t.plaintext, &mut buf).unwrap(), t.ciphertext);
+        assert_eq!(kwp.unwrap(t.ciphertext, &mut out).unwrap(), t.plaintext);
+
+        let n = t.ciphertext.len();
+        buf[..n].copy_from_slice(t.ciphertext);
+        buf[n - 1] ^= 1;
+        let res = kwp.unwrap(&buf[..n], &mut out);
+        assert_eq!(res, Err(KeyWrapError::IntegrityCheckFailed));
+    }
+}
+
+/// Run padding test using the provided test vector.
+///
+/// Messages of 0, 7, 8 and 9 bytes are padded to 8 byte blocks and compared
+/// with the expected padding, messages of 0, 15, 16 and 17 bytes are padded
+/// to 16 byte blocks. Every padded message is unpadded back. Also checks
+/// that malformed padding is rejected and tests the constant-time helpers
+/// used by unpadding.
+pub fn run_padding_test<P: Padding>(t: &PaddingTest) {
+    let msg = [0xffu8; 17];
+    let mut buf = [0u8; 32];
+    for (&n, pad) in [0, 7, 8, 9].iter().zip(t.padding.iter()) {
+        buf[..n].copy_from_slice(&msg[..n]);
+        let len = {
+            let padded = P::pad::<U8>(&mut buf, n).unwrap();
+            assert_eq!(padded[n..], pad[..]);
+            padded.len()
+        };
+        assert_eq!(P::unpad::<U8>(&buf[..len]).unwrap(), &msg[..n]);
+
+        // buffer without space for padding
+        let res = P::pad::<U8>(&mut buf[..n], n);
+        assert_eq!(res.is_ok(), pad.is_empty());
+    }
+
+    for &n in [0, 15, 16, 17].iter() {
+        buf[..n].copy_from_slice(&msg[..n]);
+        let len = P::pad::<U16>(&mut buf, n).unwrap().len();
+        assert_eq!(len % 16, 0);
+        assert_eq!(P::unpad::<U16>(&buf[..len]).unwrap(), &msg[..n]);
+    }
+
+    for block in t.malformed.iter() {
+        assert!(P::unpad_block(block).is_err());
+        assert!(P::unpad::<U8>(block).is_err());
+    }
+    assert!(P::unpad::<U8>(&buf[..7]).is_err());
+    // only zero padding allows empty messages without padding
+    assert_eq!(P::unpad::<U8>(&[]).is_ok(), t.padding[0].is_empty());
+
+    // only the last block is unpadded, zeros in preceding blocks are kept
+    let mut data = [0u8; 16];
+    data[0] = 1;
+    data[8..8 + t.padding[0].len()].copy_from_slice(t.padding[0]);
+    assert_eq!(P::unpad::<U8>(&data).unwrap(), &data[..8]);
+
+    for &(a, b, r) in [
+        (0, 0, 0), (1, 0, 1), (0, 1, 1), (0x80, 0, 1), (0x7f, 0x80, 1),
+        (0xff, 0xff, 0), (0xff, 0xfe, 1),
+    ].iter() {
+        assert_eq!(padding::ct_ne(a, b), r);
+    }
+    for &(a, b, r) in [
+        (0, 0, 0), (0, 1, 1), (1, 0, 0), (5, 5, 0), (254, 255, 1),
+        (255, 254, 0), (0, 0x7fff_ffff, 1), (0x7fff_ffff, 0, 0),
+    ].iter() {
+        assert_eq!(padding::ct_lt(a, b), r);
+    }
+    for &(flag, a, b, r) in [
+        (1, 5, 7, 5), (0, 5, 7, 7), (1, !0, 0, !0), (0, !0, 0, 0),
+        (1, 0, !0, 0), (0, 0, !0, !0),
+    ].iter() {
+        assert_eq!(padding::ct_select(flag, a, b), r);
+    }
+}
+
+/// Define block mode test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_test!(cbc_aes128, Cbc<Aes128>, CBC_AES128);
+/// ```
+#[macro_export]
+macro_rules! new_test {
+    ($name:ident, $mode:ty, $test:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_test, $test};
+
+            run_test::<_, $mode>(&$test);
+        }
+    }
+}
+
+/// Define stream cipher mode test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_stream_test!(ctr_aes128, Ctr<Aes128>, CTR_AES128);
+/// ```
+#[macro_export]
+macro_rules! new_stream_test {
+    ($name:ident, $mode:ty, $test:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_stream_test, $test};
+
+            run_stream_test::<$mode>(&$test);
+        }
+    }
+}
+
+/// Define synchronous stream cipher mode test for modes which do not
+/// support seeking
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_stream_core_test!(ofb_aes128, Ofb<Aes128>, OFB_AES128);
+/// ```
+#[macro_export]
+macro_rules! new_stream_core_test {
+    ($name:ident, $mode:ty, $test:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_stream_core_test, $test};
+
+            run_stream_core_test::<$mode>(&$test);
+        }
+    }
+}
+
+/// Define asynchronous stream cipher mode test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_async_test!(cfb_aes128, Cfb<Aes128>, CFB_AES128);
+/// ```
+#[macro_export]
+macro_rules! new_async_test {
+    ($name:ident, $mode:ty, $test:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_async_test, $test};
+
+            run_async_test::<$mode>(&$test);
+        }
+    }
+}
+
+/// Define XTS test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_xts_test!(xts_aes128, Aes128, XTS_AES128);
+/// ```
+#[macro_export]
+macro_rules! new_xts_test {
+    ($name:ident, $cipher:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_xts_test, $tests};
+
+            run_xts_test::<$cipher>($tests);
+        }
+    }
+}
+
+/// Define KW test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_kw_test!(kw_aes128, Aes128, KW_AES128);
+/// ```
+#[macro_export]
+macro_rules! new_kw_test {
+    ($name:ident, $cipher:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_kw_test, $tests};
+
+            run_kw_test::<$cipher>($tests);
+        }
+    }
+}
+
+/// Define KWP test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_kwp_test!(kwp_aes192, Aes192, KWP_AES192);
+/// ```
+#[macro_export]
+macro_rules! new_kwp_test {
+    ($name:ident, $cipher:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_kwp_test, $tests};
+
+            run_kwp_test::<$cipher>($tests);
+        }
+    }
+}
+
+/// Define padding test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// block_modes::new_padding_test!(pkcs7, Pkcs7, PKCS7_PADDING);
+/// ```
+#[macro_export]
+macro_rules! new_padding_test {
+    ($name:ident, $padding:ty, $test:ident) => {
+        #[test]
+        fn $name() {
+            use block_modes::dev::{run_padding_test, $test};
+
+            run_padding_test::<$padding>(&$test);
+        }
+    }
+}
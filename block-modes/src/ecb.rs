@@ -0,0 +1,32 @@
+use block_cipher_trait::BlockCipher;
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::U0;
+use utils::Block;
+use BlockMode;
+
+/// [Electronic Codebook][1] (ECB) block cipher mode instance.
+///
+/// Every block is processed independently, so identical plaintext blocks
+/// result in identical ciphertext blocks. Do not use it unless you know
+/// what you are doing.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#ECB
+pub struct Ecb<C: BlockCipher> {
+    cipher: C,
+}
+
+impl<C: BlockCipher> BlockMode<C> for Ecb<C> {
+    type IvSize = U0;
+
+    fn new(cipher: C, _iv: &GenericArray<u8, U0>) -> Self {
+        Ecb { cipher }
+    }
+
+    fn encrypt_blocks(&mut self, blocks: &mut [Block<C>]) {
+        self.cipher.encrypt_slice(blocks);
+    }
+
+    fn decrypt_blocks(&mut self, blocks: &mut [Block<C>]) {
+        self.cipher.decrypt_slice(blocks);
+    }
+}
@@ -0,0 +1,102 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+/// Block mode error
+///
+/// Returned when data length is not a multiple of the block size or when
+/// padding is malformed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BlockModeError;
+
+/// Error which returned when message can not be padded
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PadError;
+
+/// Error which returned when padding is malformed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UnpadError;
+
+/// Key wrap error
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyWrapError {
+    /// Input or output buffer has invalid length
+    InvalidDataLength,
+    /// Integrity check of the unwrapped key has failed
+    IntegrityCheckFailed,
+}
+
+/// Error which returned when key or IV has invalid length
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct InvalidKeyIvLength;
+
+impl fmt::Display for BlockModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("block mode error")
+    }
+}
+
+impl fmt::Display for PadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("padding error")
+    }
+}
+
+impl fmt::Display for UnpadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unpadding error")
+    }
+}
+
+impl fmt::Display for KeyWrapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            KeyWrapError::InvalidDataLength => "invalid data length",
+            KeyWrapError::IntegrityCheckFailed => "integrity check failed",
+        })
+    }
+}
+
+impl fmt::Display for InvalidKeyIvLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid key or IV length")
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for BlockModeError {
+    fn description(&self) -> &str {
+        "block mode error"
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for PadError {
+    fn description(&self) -> &str {
+        "padding error"
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for UnpadError {
+    fn description(&self) -> &str {
+        "unpadding error"
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for KeyWrapError {
+    fn description(&self) -> &str {
+        match *self {
+            KeyWrapError::InvalidDataLength => "invalid data length",
+            KeyWrapError::IntegrityCheckFailed => "integrity check failed",
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for InvalidKeyIvLength {
+    fn description(&self) -> &str {
+        "invalid key or IV length"
+    }
+}
@@ -0,0 +1,270 @@
+use block_cipher_trait::{
+    BlockDecrypt, BlockEncrypt, InvalidKey, NewBlockCipher,
+};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::U16;
+use errors::KeyWrapError;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Default initial value defined in RFC 3394
+const IV: [u8; 8] = [0xa6; 8];
+/// Alternative initial value prefix defined in RFC 5649
+const AIV: [u8; 4] = [0xa6, 0x59, 0x59, 0xa6];
+
+/// Wrapping function W, `buf` contains space for the integrity check
+/// register followed by `n >= 2` 64-bit plaintext blocks.
+fn wrap_core<C>(cipher: &C, iv: &[u8; 8], buf: &mut [u8])
+    where C: BlockEncrypt<BlockSize = U16>
+{
+    let n = buf.len() / 8 - 1;
+    let mut block = GenericArray::<u8, U16>::default();
+    block[..8].copy_from_slice(iv);
+    for j in 0..6 {
+        for i in 1..(n + 1) {
+            block[8..].copy_from_slice(&buf[8 * i..8 * i + 8]);
+            cipher.encrypt_block(&mut block);
+            let t = (n * j + i) as u64;
+            for (k, b) in block[..8].iter_mut().enumerate() {
+                *b ^= (t >> (56 - 8 * k)) as u8;
+            }
+            buf[8 * i..8 * i + 8].copy_from_slice(&block[8..]);
+        }
+    }
+    buf[..8].copy_from_slice(&block[..8]);
+}
+
+/// Unwrapping function W^-1, returns the integrity check register and
+/// leaves plaintext in `buf[8..]`.
+fn unwrap_core<C>(cipher: &C, buf: &mut [u8]) -> [u8; 8]
+    where C: BlockDecrypt<BlockSize = U16>
+{
+    let n = buf.len() / 8 - 1;
+    let mut block = GenericArray::<u8, U16>::default();
+    block[..8].copy_from_slice(&buf[..8]);
+    for j in (0..6).rev() {
+        for i in (1..(n + 1)).rev() {
+            let t = (n * j + i) as u64;
+            for (k, b) in block[..8].iter_mut().enumerate() {
+                *b ^= (t >> (56 - 8 * k)) as u8;
+            }
+            block[8..].copy_from_slice(&buf[8 * i..8 * i + 8]);
+            cipher.decrypt_block(&mut block);
+            buf[8 * i..8 * i + 8].copy_from_slice(&block[8..]);
+        }
+    }
+    let mut a = [0u8; 8];
+    a.copy_from_slice(&block[..8]);
+    a
+}
+
+/// Returns 0 if slices are equal, non-zero value otherwise. Runs in
+/// constant time for slices of the same length.
+fn ct_diff(a: &[u8], b: &[u8]) -> u8 {
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y))
+}
+
+/// Key wrap (KW) instance as defined in [RFC 3394][1].
+///
+/// Keys to wrap must be a multiple of 8 bytes and at least 16 bytes long,
+/// wrapped key is 8 bytes longer than the original one.
+///
+/// [1]: https://tools.ietf.org/html/rfc3394
+pub struct Kw<C> {
+    cipher: C,
+}
+
+impl<C> Kw<C> {
+    /// Create new KW instance from initialized key-encryption cipher.
+    pub fn new(cipher: C) -> Self {
+        Kw { cipher }
+    }
+
+    /// Create new KW instance from key-encryption key of variable size.
+    pub fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey>
+        where C: NewBlockCipher
+    {
+        Ok(Self::new(C::new_varkey(key)?))
+    }
+}
+
+impl<C: BlockEncrypt<BlockSize = U16>> Kw<C> {
+    /// Wrap `data` into `out` and return the wrapped key.
+    ///
+    /// `out` must be at least 8 bytes longer than `data`.
+    pub fn wrap<'a>(&self, data: &[u8], out: &'a mut [u8])
+        -> Result<&'a [u8], KeyWrapError>
+    {
+        let n = data.len();
+        if n < 16 || n % 8 != 0 || out.len() < n + 8 {
+            return Err(KeyWrapError::InvalidDataLength);
+        }
+        let out = &mut out[..n + 8];
+        out[8..].copy_from_slice(data);
+        wrap_core(&self.cipher, &IV, out);
+        Ok(out)
+    }
+
+    /// Wrap `data` into a newly allocated vector.
+    #[cfg(feature = "std")]
+    pub fn wrap_vec(&self, data: &[u8]) -> Result<Vec<u8>, KeyWrapError> {
+        let mut out = vec![0u8; data.len() + 8];
+        self.wrap(data, &mut out)?;
+        Ok(out)
+    }
+}
+
+impl<C: BlockDecrypt<BlockSize = U16>> Kw<C> {
+    /// Unwrap `data` into `out` and return the unwrapped key.
+    ///
+    /// `out` must be at least as long as `data`, it's used as a scratch
+    /// buffer. If integrity check fails, `out` is zeroized and
+    /// `Err(KeyWrapError::IntegrityCheckFailed)` is returned.
+    pub fn unwrap<'a>(&self, data: &[u8], out: &'a mut [u8])
+        -> Result<&'a [u8], KeyWrapError>
+    {
+        let n = data.len();
+        if n < 24 || n % 8 != 0 || out.len() < n {
+            return Err(KeyWrapError::InvalidDataLength);
+        }
+        let out = &mut out[..n];
+        out.copy_from_slice(data);
+        let a = unwrap_core(&self.cipher, out);
+        if ct_diff(&a, &IV) != 0 {
+            for b in out.iter_mut() { *b = 0; }
+            return Err(KeyWrapError::IntegrityCheckFailed);
+        }
+        Ok(&out[8..])
+    }
+
+    /// Unwrap `data` into a newly allocated vector.
+    #[cfg(feature = "std")]
+    pub fn unwrap_vec(&self, data: &[u8]) -> Result<Vec<u8>, KeyWrapError> {
+        let mut out = vec![0u8; data.len()];
+        let n = self.unwrap(data, &mut out)?.len();
+        out.drain(..8);
+        out.truncate(n);
+        Ok(out)
+    }
+}
+
+/// Key wrap with padding (KWP) instance as defined in [RFC 5649][1].
+///
+/// Keys of any non-zero length up to 2^32-1 bytes can be wrapped, wrapped
+/// key has length of the original key rounded up to a multiple of 8 plus
+/// 8 bytes.
+///
+/// [1]: https://tools.ietf.org/html/rfc5649
+pub struct Kwp<C> {
+    cipher: C,
+}
+
+impl<C> Kwp<C> {
+    /// Create new KWP instance from initialized key-encryption cipher.
+    pub fn new(cipher: C) -> Self {
+        Kwp { cipher }
+    }
+
+    /// Create new KWP instance from key-encryption key of variable size.
+    pub fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey>
+        where C: NewBlockCipher
+    {
+        Ok(Self::new(C::new_varkey(key)?))
+    }
+
+    /// Return length of the wrapped key for the key of length `len`.
+    pub fn wrapped_len(len: usize) -> usize {
+        (len + 7) / 8 * 8 + 8
+    }
+}
+
+impl<C: BlockEncrypt<BlockSize = U16>> Kwp<C> {
+    /// Wrap `data` into `out` and return the wrapped key.
+    ///
+    /// `out` must be at least `Kwp::wrapped_len(data.len())` bytes long.
+    pub fn wrap<'a>(&self, data: &[u8], out: &'a mut [u8])
+        -> Result<&'a [u8], KeyWrapError>
+    {
+        let n = data.len();
+        let len = Self::wrapped_len(n);
+        if n == 0 || n as u64 > 0xffff_ffff || out.len() < len {
+            return Err(KeyWrapError::InvalidDataLength);
+        }
+        let mut iv = [0u8; 8];
+        iv[..4].copy_from_slice(&AIV);
+        for (i, b) in iv[4..].iter_mut().enumerate() {
+            *b = (n as u64 >> (24 - 8 * i)) as u8;
+        }
+
+        let out = &mut out[..len];
+        out[8..8 + n].copy_from_slice(data);
+        for b in out[8 + n..].iter_mut() { *b = 0; }
+        if len == 16 {
+            out[..8].copy_from_slice(&iv);
+            self.cipher.encrypt_block(GenericArray::from_mut_slice(out));
+        } else {
+            wrap_core(&self.cipher, &iv, out);
+        }
+        Ok(out)
+    }
+
+    /// Wrap `data` into a newly allocated vector.
+    #[cfg(feature = "std")]
+    pub fn wrap_vec(&self, data: &[u8]) -> Result<Vec<u8>, KeyWrapError> {
+        let mut out = vec![0u8; Self::wrapped_len(data.len())];
+        self.wrap(data, &mut out)?;
+        Ok(out)
+    }
+}
+
+impl<C: BlockDecrypt<BlockSize = U16>> Kwp<C> {
+    /// Unwrap `data` into `out` and return the unwrapped key.
+    ///
+    /// `out` must be at least as long as `data`, it's used as a scratch
+    /// buffer. If integrity check fails, `out` is zeroized and
+    /// `Err(KeyWrapError::IntegrityCheckFailed)` is returned.
+    pub fn unwrap<'a>(&self, data: &[u8], out: &'a mut [u8])
+        -> Result<&'a [u8], KeyWrapError>
+    {
+        let n = data.len();
+        if n < 16 || n % 8 != 0 || out.len() < n {
+            return Err(KeyWrapError::InvalidDataLength);
+        }
+        let out = &mut out[..n];
+        out.copy_from_slice(data);
+        let a = if n == 16 {
+            self.cipher.decrypt_block(GenericArray::from_mut_slice(out));
+            let mut a = [0u8; 8];
+            a.copy_from_slice(&out[..8]);
+            a
+        } else {
+            unwrap_core(&self.cipher, out)
+        };
+
+        let mli = a[4..].iter().fold(0usize, |acc, &b| (acc << 8) | b as usize);
+        let padded = n - 8;
+        // message length must satisfy `padded - 8 < mli <= padded`, after
+        // that padding bytes must be zero
+        let mut bad = ct_diff(&a[..4], &AIV);
+        if mli > padded || mli + 8 <= padded {
+            bad |= 1;
+        } else {
+            bad |= out[8 + mli..].iter().fold(0, |acc, &b| acc | b);
+        }
+        if bad != 0 {
+            for b in out.iter_mut() { *b = 0; }
+            return Err(KeyWrapError::IntegrityCheckFailed);
+        }
+        Ok(&out[8..8 + mli])
+    }
+
+    /// Unwrap `data` into a newly allocated vector.
+    #[cfg(feature = "std")]
+    pub fn unwrap_vec(&self, data: &[u8]) -> Result<Vec<u8>, KeyWrapError> {
+        let mut out = vec![0u8; data.len()];
+        let n = self.unwrap(data, &mut out)?.len();
+        out.drain(..8);
+        out.truncate(n);
+        Ok(out)
+    }
+}
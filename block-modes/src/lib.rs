@@ -0,0 +1,173 @@
+//! This crate contains generic implementations of block cipher modes of
+//! operation, which work over any `BlockCipher` from the `block-cipher-trait`
+//! crate.
+//!
+//! Modes which process data in whole blocks implement the `BlockMode` trait,
+//! while modes which turn block cipher into a stream cipher implement traits
+//! from the `stream-cipher` crate: CTR and OFB are synchronous stream
+//! ciphers, while CFB variants implement `AsyncStreamCipher`. Stream cipher
+//! modes use only the encryption direction, so they require just
+//! `BlockEncrypt`.
+//!
+//! See [RustCrypto/block-ciphers](https://github.com/RustCrypto/block-ciphers)
+//! for ciphers implementation.
+#![no_std]
+pub extern crate block_cipher_trait;
+pub extern crate stream_cipher;
+
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate std;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use block_cipher_trait::BlockCipher;
+use block_cipher_trait::generic_array::{GenericArray, ArrayLength};
+use block_cipher_trait::generic_array::typenum::Unsigned;
+
+mod errors;
+mod utils;
+mod ecb;
+mod cbc;
+mod pcbc;
+mod ctr;
+mod cfb;
+mod cfb8;
+mod cfb1;
+mod ofb;
+mod xts;
+mod kw;
+pub mod padding;
+#[cfg(feature = "dev")]
+pub mod dev;
+
+pub use errors::{
+    BlockModeError, InvalidKeyIvLength, KeyWrapError, PadError, UnpadError,
+};
+pub use ecb::Ecb;
+pub use cbc::Cbc;
+pub use pcbc::Pcbc;
+pub use ctr::{Ctr, CtrFlavor, Ctr128BE, Ctr32BE, Ctr64LE};
+pub use cfb::Cfb;
+pub use cfb8::Cfb8;
+pub use cfb1::Cfb1;
+pub use ofb::Ofb;
+pub use xts::Xts;
+pub use kw::{Kw, Kwp};
+
+use padding::Padding;
+use block_cipher_trait::to_blocks;
+use utils::Block;
+
+/// Trait for block cipher modes of operation which process data in
+/// whole blocks.
+///
+/// Mode instance keeps chaining state between calls, so a message can be
+/// processed in several parts.
+pub trait BlockMode<C: BlockCipher>: core::marker::Sized {
+    /// Size of the initialization vector in bytes
+    type IvSize: ArrayLength<u8>;
+
+    /// Create new block mode instance from initialized block cipher and IV.
+    fn new(cipher: C, iv: &GenericArray<u8, Self::IvSize>) -> Self;
+
+    /// Create new block mode instance from key and IV with variable sizes.
+    ///
+    /// Key is passed to `NewBlockCipher::new_varkey`, IV length must be equal
+    /// to `IvSize`.
+    fn new_varkey(key: &[u8], iv: &[u8]) -> Result<Self, InvalidKeyIvLength> {
+        if iv.len() != Self::IvSize::to_usize() {
+            return Err(InvalidKeyIvLength);
+        }
+        let cipher = C::new_varkey(key).map_err(|_| InvalidKeyIvLength)?;
+        Ok(Self::new(cipher, GenericArray::from_slice(iv)))
+    }
+
+    /// Encrypt blocks of data in-place.
+    fn encrypt_blocks(&mut self, blocks: &mut [Block<C>]);
+
+    /// Decrypt blocks of data in-place.
+    fn decrypt_blocks(&mut self, blocks: &mut [Block<C>]);
+
+    /// Encrypt message in-place.
+    ///
+    /// Length of `buffer` must be a multiple of the block size, otherwise
+    /// `Err(BlockModeError)` will be returned without modifying `buffer`.
+    fn encrypt_nopad(&mut self, buffer: &mut [u8])
+        -> Result<(), BlockModeError>
+    {
+        if buffer.len() % C::BlockSize::to_usize() != 0 {
+            return Err(BlockModeError);
+        }
+        self.encrypt_blocks(to_blocks(buffer));
+        Ok(())
+    }
+
+    /// Decrypt message in-place.
+    ///
+    /// Length of `buffer` must be a multiple of the block size, otherwise
+    /// `Err(BlockModeError)` will be returned without modifying `buffer`.
+    fn decrypt_nopad(&mut self, buffer: &mut [u8])
+        -> Result<(), BlockModeError>
+    {
+        if buffer.len() % C::BlockSize::to_usize() != 0 {
+            return Err(BlockModeError);
+        }
+        self.decrypt_blocks(to_blocks(buffer));
+        Ok(())
+    }
+
+    /// Pad and encrypt message in-place and return the resulting
+    /// ciphertext.
+    ///
+    /// Message of length `pos` is stored at the beginning of `buffer`,
+    /// which must have enough space for the padding, otherwise
+    /// `Err(BlockModeError)` will be returned.
+    fn encrypt_pad<P: Padding>(mut self, buffer: &mut [u8], pos: usize)
+        -> Result<&[u8], BlockModeError>
+    {
+        let buf = P::pad::<C::BlockSize>(buffer, pos)
+            .map_err(|_| BlockModeError)?;
+        self.encrypt_nopad(buf)?;
+        Ok(buf)
+    }
+
+    /// Decrypt message in-place and unpad it, returning the resulting
+    /// plaintext.
+    ///
+    /// Malformed padding and invalid `buffer` length result in the same
+    /// `Err(BlockModeError)`.
+    fn decrypt_pad<P: Padding>(mut self, buffer: &mut [u8])
+        -> Result<&[u8], BlockModeError>
+    {
+        self.decrypt_nopad(buffer)?;
+        P::unpad::<C::BlockSize>(buffer).map_err(|_| BlockModeError)
+    }
+
+    /// Pad and encrypt message into a newly allocated vector.
+    #[cfg(feature = "std")]
+    fn encrypt_vec<P: Padding>(self, plaintext: &[u8]) -> Vec<u8> {
+        let bs = C::BlockSize::to_usize();
+        let pos = plaintext.len();
+        let mut buf = Vec::with_capacity(pos + bs);
+        buf.extend_from_slice(plaintext);
+        buf.resize(pos + bs, 0);
+        let n = self.encrypt_pad::<P>(&mut buf, pos)
+            .expect("enough space for padding is allocated")
+            .len();
+        buf.truncate(n);
+        buf
+    }
+
+    /// Decrypt and unpad message into a newly allocated vector.
+    #[cfg(feature = "std")]
+    fn decrypt_vec<P: Padding>(self, ciphertext: &[u8])
+        -> Result<Vec<u8>, BlockModeError>
+    {
+        let mut buf = ciphertext.to_vec();
+        let n = self.decrypt_pad::<P>(&mut buf)?.len();
+        buf.truncate(n);
+        Ok(buf)
+    }
+}
@@ -0,0 +1,75 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::Unsigned;
+use stream_cipher::{LoopError, NewFixStreamCipher, StreamCipherCore};
+use core::cmp;
+use utils::{Block, xor, xor_b2b};
+
+/// [Output Feedback][1] (OFB) mode instance.
+///
+/// Keystream does not depend on the data, so OFB is a synchronous stream
+/// cipher, but every keystream block depends on the previous one, so it
+/// can not be generated in parallel.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#OFB
+pub struct Ofb<C: BlockEncrypt> {
+    cipher: C,
+    /// Current keystream block
+    iv: Block<C>,
+    pos: usize,
+}
+
+impl<C: BlockEncrypt> Ofb<C> {
+    /// Create new OFB mode instance from initialized block cipher and IV.
+    pub fn from_cipher(cipher: C, iv: &Block<C>) -> Self {
+        Ofb { cipher, iv: iv.clone(), pos: C::BlockSize::to_usize() }
+    }
+
+    /// Generate `len` bytes of keystream and pass it to `f` in chunks
+    /// together with their offsets.
+    fn keystream<G: FnMut(usize, &[u8])>(&mut self, len: usize, mut f: G) {
+        let bs = C::BlockSize::to_usize();
+        let mut i = 0;
+        while i < len {
+            if self.pos == bs {
+                self.cipher.encrypt_block(&mut self.iv);
+                self.pos = 0;
+            }
+            let n = cmp::min(bs - self.pos, len - i);
+            f(i, &self.iv[self.pos..self.pos + n]);
+            self.pos += n;
+            i += n;
+        }
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> NewFixStreamCipher for Ofb<C> {
+    type KeySize = C::KeySize;
+    type NonceSize = C::BlockSize;
+
+    fn new(key: &GenericArray<u8, C::KeySize>, iv: &Block<C>) -> Self {
+        Self::from_cipher(C::new(key), iv)
+    }
+}
+
+impl<C: BlockEncrypt> StreamCipherCore for Ofb<C> {
+    fn try_apply_keystream(&mut self, data: &mut [u8])
+        -> Result<(), LoopError>
+    {
+        self.keystream(data.len(), |i, ks| {
+            xor(&mut data[i..i + ks.len()], ks);
+        });
+        Ok(())
+    }
+
+    fn try_apply_keystream_b2b(&mut self, input: &[u8], output: &mut [u8])
+        -> Result<(), LoopError>
+    {
+        assert_eq!(input.len(), output.len());
+        self.keystream(input.len(), |i, ks| {
+            let n = ks.len();
+            xor_b2b(&input[i..i + n], ks, &mut output[i..i + n]);
+        });
+        Ok(())
+    }
+}
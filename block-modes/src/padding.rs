@@ -0,0 +1,263 @@
+//! Padding schemes for block-oriented buffers.
+//!
+//! Unpadding runs in constant time with respect to the content of the
+//! last block, only the resulting message length is observable. Block size
+//! must not exceed 255 bytes.
+use block_cipher_trait::generic_array::ArrayLength;
+use errors::{PadError, UnpadError};
+
+/// Trait for padding messages divided into blocks
+pub trait Padding {
+    /// Pad `block`, message part of which occupies `block[..pos]`.
+    ///
+    /// Returns `Err(PadError)` if `pos` can not be padded by this scheme.
+    fn pad_block(block: &mut [u8], pos: usize) -> Result<(), PadError>;
+
+    /// Return length of the message part of the last `block`.
+    ///
+    /// Returns `Err(UnpadError)` if padding is malformed.
+    fn unpad_block(block: &[u8]) -> Result<usize, UnpadError>;
+
+    /// Pad message of length `pos` stored at the beginning of `buf` to the
+    /// block size `N` and return the padded message.
+    ///
+    /// Returns `Err(PadError)` if `buf` is too small to contain the padding.
+    fn pad<N: ArrayLength<u8>>(buf: &mut [u8], pos: usize)
+        -> Result<&mut [u8], PadError>
+    {
+        let bs = N::to_usize();
+        if bs > 255 || pos > buf.len() {
+            return Err(PadError);
+        }
+        let start = pos - pos % bs;
+        if start + bs > buf.len() {
+            return Err(PadError);
+        }
+        Self::pad_block(&mut buf[start..start + bs], pos - start)?;
+        Ok(&mut buf[..start + bs])
+    }
+
+    /// Unpad message `data` which consists of blocks of size `N`.
+    ///
+    /// Returns `Err(UnpadError)` if `data` is empty, its length is not a
+    /// multiple of the block size or padding is malformed.
+    fn unpad<N: ArrayLength<u8>>(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let bs = N::to_usize();
+        if bs > 255 || data.is_empty() || data.len() % bs != 0 {
+            return Err(UnpadError);
+        }
+        let start = data.len() - bs;
+        let n = Self::unpad_block(&data[start..])?;
+        Ok(&data[..start + n])
+    }
+}
+
+/// Returns 1 if `a != b` and 0 otherwise
+#[inline(always)]
+pub(crate) fn ct_ne(a: u8, b: u8) -> u8 {
+    let x = a ^ b;
+    (x | x.wrapping_neg()) >> 7
+}
+
+/// Returns 1 if `a < b` and 0 otherwise, both values must be less
+/// than 2^31
+#[inline(always)]
+pub(crate) fn ct_lt(a: usize, b: usize) -> u8 {
+    ((a as u32).wrapping_sub(b as u32) >> 31) as u8
+}
+
+/// Returns `a` if `flag` equals to 1 and `b` if it equals to 0
+#[inline(always)]
+pub(crate) fn ct_select(flag: u8, a: usize, b: usize) -> usize {
+    let mask = (flag as usize).wrapping_neg();
+    (a & mask) | (b & !mask)
+}
+
+/// Check that padding length byte `n` is in the `1..=block.len()` range
+#[inline(always)]
+fn ct_bad_len(block: &[u8]) -> (usize, u8) {
+    let n = block[block.len() - 1] as usize;
+    let bad = ct_lt(n, 1) | ct_lt(block.len(), n);
+    (n, bad)
+}
+
+#[inline(always)]
+fn check_pos(block: &[u8], pos: usize) -> Result<(), PadError> {
+    if block.is_empty() || block.len() > 255 || pos >= block.len() {
+        Err(PadError)
+    } else {
+        Ok(())
+    }
+}
+
+#[inline(always)]
+fn to_result(bad: u8, len: usize) -> Result<usize, UnpadError> {
+    if bad == 0 { Ok(len) } else { Err(UnpadError) }
+}
+
+/// Pad block with bytes with value equal to the number of bytes added.
+///
+/// PKCS#7 described in the [RFC 5652](https://tools.ietf.org/html/rfc5652#section-6.3).
+pub enum Pkcs7 {}
+
+impl Padding for Pkcs7 {
+    fn pad_block(block: &mut [u8], pos: usize) -> Result<(), PadError> {
+        check_pos(block, pos)?;
+        let n = (block.len() - pos) as u8;
+        for b in block[pos..].iter_mut() { *b = n; }
+        Ok(())
+    }
+
+    fn unpad_block(block: &[u8]) -> Result<usize, UnpadError> {
+        if block.is_empty() || block.len() > 255 {
+            return Err(UnpadError);
+        }
+        let bs = block.len();
+        let (n, mut bad) = ct_bad_len(block);
+        for (i, &b) in block.iter().enumerate() {
+            let in_pad = ct_lt(bs - 1 - i, n);
+            bad |= in_pad & ct_ne(b, n as u8);
+        }
+        to_result(bad, bs.wrapping_sub(n))
+    }
+}
+
+/// Pad block with zeros except the last byte which will be set to the
+/// number of bytes added.
+///
+/// ANSI X.923 padding.
+pub enum AnsiX923 {}
+
+impl Padding for AnsiX923 {
+    fn pad_block(block: &mut [u8], pos: usize) -> Result<(), PadError> {
+        check_pos(block, pos)?;
+        let n = block.len() - pos;
+        for b in block[pos..].iter_mut() { *b = 0; }
+        block[pos + n - 1] = n as u8;
+        Ok(())
+    }
+
+    fn unpad_block(block: &[u8]) -> Result<usize, UnpadError> {
+        if block.is_empty() || block.len() > 255 {
+            return Err(UnpadError);
+        }
+        let bs = block.len();
+        let (n, mut bad) = ct_bad_len(block);
+        for (i, &b) in block[..bs - 1].iter().enumerate() {
+            let in_pad = ct_lt(bs - 1 - i, n);
+            bad |= in_pad & ct_ne(b, 0);
+        }
+        to_result(bad, bs.wrapping_sub(n))
+    }
+}
+
+/// Pad block with byte `0x80` followed by zeros.
+///
+/// ISO/IEC 7816-4 padding, also known as bit padding.
+pub enum Iso7816 {}
+
+impl Padding for Iso7816 {
+    fn pad_block(block: &mut [u8], pos: usize) -> Result<(), PadError> {
+        check_pos(block, pos)?;
+        block[pos] = 0x80;
+        for b in block[pos + 1..].iter_mut() { *b = 0; }
+        Ok(())
+    }
+
+    fn unpad_block(block: &[u8]) -> Result<usize, UnpadError> {
+        if block.is_empty() || block.len() > 255 {
+            return Err(UnpadError);
+        }
+        // find the last non-zero byte and its value
+        let mut found = 0u8;
+        let mut pos = 0usize;
+        let mut val = 0usize;
+        for (i, &b) in block.iter().enumerate() {
+            let nz = ct_ne(b, 0);
+            found |= nz;
+            pos = ct_select(nz, i, pos);
+            val = ct_select(nz, b as usize, val);
+        }
+        let bad = (found ^ 1) | ct_ne(val as u8, 0x80);
+        to_result(bad, pos)
+    }
+}
+
+/// Pad block with arbitrary bytes ending with a byte equal to the number
+/// of bytes added.
+///
+/// ISO 10126 padding. This crate does not have access to a source of
+/// randomness, so filler bytes are set to zero. Unpadding accepts any
+/// filler bytes.
+pub enum Iso10126 {}
+
+impl Padding for Iso10126 {
+    fn pad_block(block: &mut [u8], pos: usize) -> Result<(), PadError> {
+        AnsiX923::pad_block(block, pos)
+    }
+
+    fn unpad_block(block: &[u8]) -> Result<usize, UnpadError> {
+        if block.is_empty() || block.len() > 255 {
+            return Err(UnpadError);
+        }
+        let (n, bad) = ct_bad_len(block);
+        to_result(bad, block.len().wrapping_sub(n))
+    }
+}
+
+/// Pad block with zeros.
+///
+/// Messages which already fill the whole block are not padded. Unpadding
+/// strips trailing zeros only within the last block, e.g. 16 bytes
+/// `[1, 0, .., 0]` are unpadded to 8 bytes with 8 byte blocks. This scheme
+/// should be used only with messages which can not end with zero bytes.
+pub enum ZeroPadding {}
+
+impl Padding for ZeroPadding {
+    fn pad_block(block: &mut [u8], pos: usize) -> Result<(), PadError> {
+        if pos > block.len() {
+            return Err(PadError);
+        }
+        for b in block[pos..].iter_mut() { *b = 0; }
+        Ok(())
+    }
+
+    fn pad<N: ArrayLength<u8>>(buf: &mut [u8], pos: usize)
+        -> Result<&mut [u8], PadError>
+    {
+        let bs = N::to_usize();
+        if pos > buf.len() {
+            return Err(PadError);
+        }
+        if pos % bs == 0 {
+            return Ok(&mut buf[..pos]);
+        }
+        let end = pos - pos % bs + bs;
+        if end > buf.len() {
+            return Err(PadError);
+        }
+        Self::pad_block(&mut buf[..end], pos)?;
+        Ok(&mut buf[..end])
+    }
+
+    fn unpad<N: ArrayLength<u8>>(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let bs = N::to_usize();
+        if data.len() % bs != 0 {
+            return Err(UnpadError);
+        }
+        if data.is_empty() {
+            return Ok(data);
+        }
+        let start = data.len() - bs;
+        let n = Self::unpad_block(&data[start..])?;
+        Ok(&data[..start + n])
+    }
+
+    fn unpad_block(block: &[u8]) -> Result<usize, UnpadError> {
+        let mut len = 0usize;
+        for (i, &b) in block.iter().enumerate() {
+            len = ct_select(ct_ne(b, 0), i + 1, len);
+        }
+        Ok(len)
+    }
+}
@@ -0,0 +1,59 @@
+use block_cipher_trait::BlockCipher;
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::Unsigned;
+use utils::{Block, ParBlocks, xor};
+use BlockMode;
+
+/// [Propagating Cipher Block Chaining][1] (PCBC) block cipher mode instance.
+///
+/// Encryption is sequential, while decryption processes `ParBlocks`
+/// blocks at once.
+///
+/// [1]: https://en.wikipedia.org/wiki/Block_cipher_mode_of_operation#PCBC
+pub struct Pcbc<C: BlockCipher> {
+    cipher: C,
+    iv: Block<C>,
+}
+
+impl<C: BlockCipher> BlockMode<C> for Pcbc<C> {
+    type IvSize = C::BlockSize;
+
+    fn new(cipher: C, iv: &Block<C>) -> Self {
+        Pcbc { cipher, iv: iv.clone() }
+    }
+
+    fn encrypt_blocks(&mut self, blocks: &mut [Block<C>]) {
+        for block in blocks {
+            let plaintext = block.clone();
+            xor(block, &self.iv);
+            self.cipher.encrypt_block(block);
+            self.iv = plaintext;
+            xor(&mut self.iv, block);
+        }
+    }
+
+    fn decrypt_blocks(&mut self, blocks: &mut [Block<C>]) {
+        let pb = C::ParBlocks::to_usize();
+        for chunk in blocks.chunks_mut(pb) {
+            if chunk.len() == pb {
+                let par_blocks: &mut ParBlocks<C> =
+                    GenericArray::from_mut_slice(chunk);
+                let ciphertext = par_blocks.clone();
+                self.cipher.decrypt_blocks(par_blocks);
+                for (block, ct) in par_blocks.iter_mut().zip(ciphertext.iter()) {
+                    xor(block, &self.iv);
+                    self.iv = ct.clone();
+                    xor(&mut self.iv, block);
+                }
+            } else {
+                for block in chunk {
+                    let ciphertext = block.clone();
+                    self.cipher.decrypt_block(block);
+                    xor(block, &self.iv);
+                    self.iv = ciphertext;
+                    xor(&mut self.iv, block);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use block_cipher_trait::BlockCipherBase;
+use block_cipher_trait::generic_array::GenericArray;
+
+pub type Block<C> = GenericArray<u8, <C as BlockCipherBase>::BlockSize>;
+pub type ParBlocks<C> = GenericArray<Block<C>, <C as BlockCipherBase>::ParBlocks>;
+
+#[inline(always)]
+pub fn xor(buf: &mut [u8], key: &[u8]) {
+    debug_assert_eq!(buf.len(), key.len());
+    for (a, b) in buf.iter_mut().zip(key) {
+        *a ^= *b;
+    }
+}
+
+/// Write XOR of `input` and `key` into `output`
+#[inline(always)]
+pub fn xor_b2b(input: &[u8], key: &[u8], output: &mut [u8]) {
+    debug_assert_eq!(input.len(), key.len());
+    debug_assert_eq!(input.len(), output.len());
+    for ((o, a), b) in output.iter_mut().zip(input).zip(key) {
+        *o = *a ^ *b;
+    }
+}
@@ -0,0 +1,206 @@
+use block_cipher_trait::{
+    BlockCipherBase, BlockDecrypt, BlockEncrypt, NewBlockCipher,
+    InvalidKey, TweakableBlockCipher,
+};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::{U16, Unsigned};
+use errors::BlockModeError;
+use utils::xor;
+
+type Block = GenericArray<u8, U16>;
+
+/// [XEX-based tweaked-codebook mode with ciphertext stealing][1] (XTS)
+/// instance as defined in IEEE 1619.
+///
+/// XTS uses two instances of a block cipher with 16 byte blocks: the first
+/// one encrypts data, the second one encrypts tweak. Data is processed in
+/// sectors (data units) of at least 16 bytes, sectors which length is not
+/// a multiple of 16 are processed using ciphertext stealing.
+///
+/// [1]: https://en.wikipedia.org/wiki/Disk_encryption_theory#XTS
+pub struct Xts<C> {
+    cipher: C,
+    tweak_cipher: C,
+}
+
+/// Multiply tweak by the primitive element of GF(2^128)
+#[inline(always)]
+fn mul_alpha(tweak: &mut Block) {
+    let mut carry = 0;
+    for b in tweak.iter_mut() {
+        let next = *b >> 7;
+        *b = (*b << 1) | carry;
+        carry = next;
+    }
+    if carry != 0 {
+        tweak[0] ^= 0x87;
+    }
+}
+
+impl<C> Xts<C>
+    where C: BlockCipherBase<BlockSize = U16> + BlockEncrypt + BlockDecrypt
+{
+    /// Create new XTS instance from the data and tweak ciphers.
+    pub fn new(cipher: C, tweak_cipher: C) -> Self {
+        Xts { cipher, tweak_cipher }
+    }
+
+    /// Create new XTS instance from the concatenation of data and tweak
+    /// keys.
+    ///
+    /// Both halves of `key` are passed to `NewBlockCipher::new_varkey`,
+    /// key length errors are reported for the whole key.
+    pub fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey>
+        where C: NewBlockCipher
+    {
+        let len_err = InvalidKey::Length {
+            len: key.len(),
+            min: 2*C::MIN_KEY_SIZE,
+            max: 2*C::MAX_KEY_SIZE,
+            step: 2*C::KEY_SIZE_STEP,
+        };
+        if key.len() % 2 != 0 {
+            return Err(len_err);
+        }
+        let map_err = |err| match err {
+            InvalidKey::Length { .. } => len_err,
+            err => err,
+        };
+        let (key1, key2) = key.split_at(key.len() / 2);
+        let cipher = C::new_varkey(key1).map_err(map_err)?;
+        let tweak_cipher = C::new_varkey(key2).map_err(map_err)?;
+        Ok(Self::new(cipher, tweak_cipher))
+    }
+
+    /// Encrypt sector in-place using the given tweak (usually sector index
+    /// encoded as a little endian integer).
+    ///
+    /// Returns `Err(BlockModeError)` if sector is shorter than 16 bytes.
+    pub fn encrypt_sector(&self, sector: &mut [u8], tweak: &Block)
+        -> Result<(), BlockModeError>
+    {
+        if sector.len() < 16 {
+            return Err(BlockModeError);
+        }
+        let mut t = *tweak;
+        self.tweak_cipher.encrypt_block(&mut t);
+
+        let tail = sector.len() % 16;
+        let full = sector.len() - tail;
+        if tail == 0 {
+            self.process_blocks(sector, &mut t, true);
+            return Ok(());
+        }
+        self.process_blocks(&mut sector[..full - 16], &mut t, true);
+
+        // ciphertext stealing
+        let (last, partial) = sector[full - 16..].split_at_mut(16);
+        let mut cc = Block::clone_from_slice(last);
+        self.xex_block(&mut cc, &t, true);
+        mul_alpha(&mut t);
+        let mut pp = cc;
+        pp[..tail].copy_from_slice(partial);
+        partial.copy_from_slice(&cc[..tail]);
+        self.xex_block(&mut pp, &t, true);
+        last.copy_from_slice(&pp);
+        Ok(())
+    }
+
+    /// Decrypt sector in-place using the given tweak (usually sector index
+    /// encoded as a little endian integer).
+    ///
+    /// Returns `Err(BlockModeError)` if sector is shorter than 16 bytes.
+    pub fn decrypt_sector(&self, sector: &mut [u8], tweak: &Block)
+        -> Result<(), BlockModeError>
+    {
+        if sector.len() < 16 {
+            return Err(BlockModeError);
+        }
+        let mut t = *tweak;
+        self.tweak_cipher.encrypt_block(&mut t);
+
+        let tail = sector.len() % 16;
+        let full = sector.len() - tail;
+        if tail == 0 {
+            self.process_blocks(sector, &mut t, false);
+            return Ok(());
+        }
+        self.process_blocks(&mut sector[..full - 16], &mut t, false);
+
+        // ciphertext stealing, the last full block uses the next tweak
+        let (last, partial) = sector[full - 16..].split_at_mut(16);
+        let mut t_next = t;
+        mul_alpha(&mut t_next);
+        let mut pp = Block::clone_from_slice(last);
+        self.xex_block(&mut pp, &t_next, false);
+        let mut cp = pp;
+        cp[..tail].copy_from_slice(partial);
+        partial.copy_from_slice(&pp[..tail]);
+        self.xex_block(&mut cp, &t, false);
+        last.copy_from_slice(&cp);
+        Ok(())
+    }
+
+    /// Process single block with the encrypted tweak `t`
+    #[inline(always)]
+    fn xex_block(&self, block: &mut Block, t: &Block, encrypt: bool) {
+        xor(block, t);
+        if encrypt {
+            self.cipher.encrypt_block(block);
+        } else {
+            self.cipher.decrypt_block(block);
+        }
+        xor(block, t);
+    }
+
+    /// Process full blocks of `data` in chunks of `ParBlocks` blocks,
+    /// updating encrypted tweak `t`
+    fn process_blocks(&self, data: &mut [u8], t: &mut Block, encrypt: bool) {
+        let pb = C::ParBlocks::to_usize();
+        let mut tweaks = GenericArray::<Block, C::ParBlocks>::default();
+        for chunk in data.chunks_mut(16 * pb) {
+            let n = chunk.len() / 16;
+            let blocks = chunk.chunks_mut(16).zip(tweaks.iter_mut());
+            for (block, tweak) in blocks {
+                tweak.copy_from_slice(t);
+                xor(block, tweak);
+                mul_alpha(t);
+            }
+            if encrypt {
+                self.cipher.encrypt_bytes(chunk);
+            } else {
+                self.cipher.decrypt_bytes(chunk);
+            }
+            for (block, tweak) in chunk.chunks_mut(16).zip(tweaks[..n].iter()) {
+                xor(block, tweak);
+            }
+        }
+    }
+}
+
+impl<C> BlockCipherBase for Xts<C>
+    where C: BlockCipherBase<BlockSize = U16>
+{
+    type BlockSize = U16;
+    type ParBlocks = C::ParBlocks;
+}
+
+/// XTS processing of a single 16 byte data unit, tweak is the data unit
+/// sequence number encoded as a little endian integer.
+impl<C> TweakableBlockCipher for Xts<C>
+    where C: BlockCipherBase<BlockSize = U16> + BlockEncrypt + BlockDecrypt
+{
+    type TweakSize = U16;
+
+    fn encrypt_block_with_tweak(&self, block: &mut Block, tweak: &Block) {
+        let mut t = *tweak;
+        self.tweak_cipher.encrypt_block(&mut t);
+        self.xex_block(block, &t, true);
+    }
+
+    fn decrypt_block_with_tweak(&self, block: &mut Block, tweak: &Block) {
+        let mut t = *tweak;
+        self.tweak_cipher.encrypt_block(&mut t);
+        self.xex_block(block, &t, false);
+    }
+}
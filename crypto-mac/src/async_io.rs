@@ -0,0 +1,87 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_io::AsyncWrite;
+use std::io;
+use writer::MacWriter;
+use Mac;
+#[cfg(feature = "digest-async")]
+use core::future::Future;
+#[cfg(feature = "digest-async")]
+use digest::{ProcessAsyncReader, process_async_reader};
+#[cfg(feature = "digest-async")]
+use futures_io::AsyncRead;
+#[cfg(feature = "digest-async")]
+use reader::MacInput;
+#[cfg(feature = "digest-async")]
+use MacResult;
+
+// Wrapped `Mac` instance is never pinned
+impl<M: Mac> Unpin for MacWriter<M> {}
+
+impl<M: Mac> AsyncWrite for MacWriter<M> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.get_mut().get_mut().input(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context)
+        -> Poll<io::Result<()>>
+    {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context)
+        -> Poll<io::Result<()>>
+    {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Future returned by `finalize_mac_async_reader`.
+#[cfg(feature = "digest-async")]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct FinalizeMacAsyncReader<'a, M: Mac, R: ?Sized + 'a> {
+    inner: ProcessAsyncReader<'a, MacInput<M>, R>,
+}
+
+/// Feed all data from `reader` into `mac` without blocking and return
+/// the result of the `Mac` computation.
+///
+/// Available only with enabled `digest-async` feature, see
+/// `digest::process_async_reader` for details.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use crypto_mac::{Mac, finalize_mac_async_reader};
+///
+/// let mac = HmacSha256::new_varkey(key)?;
+/// let result = finalize_mac_async_reader(mac, &mut stream).await?;
+/// ```
+#[cfg(feature = "digest-async")]
+pub fn finalize_mac_async_reader<'a, M, R>(mac: M, reader: &'a mut R)
+    -> FinalizeMacAsyncReader<'a, M, R>
+    where M: Mac, R: AsyncRead + Unpin + ?Sized
+{
+    FinalizeMacAsyncReader {
+        inner: process_async_reader(MacInput(mac), reader),
+    }
+}
+
+#[cfg(feature = "digest-async")]
+impl<'a, M, R> Future for FinalizeMacAsyncReader<'a, M, R>
+    where M: Mac, R: AsyncRead + Unpin + ?Sized
+{
+    type Output = io::Result<MacResult<M::OutputSize>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match Pin::new(&mut self.inner).poll(cx) {
+            Poll::Ready(Ok(input)) => Poll::Ready(Ok(input.0.finalize())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use block_cipher_trait::BlockCipherBase;
+use block_cipher_trait::generic_array::{GenericArray, ArrayLength};
+
+pub type Block<C> = GenericArray<u8, <C as BlockCipherBase>::BlockSize>;
+pub type ParBlocks<C> =
+    GenericArray<Block<C>, <C as BlockCipherBase>::ParBlocks>;
+
+#[inline(always)]
+pub fn xor(buf: &mut [u8], key: &[u8]) {
+    debug_assert_eq!(buf.len(), key.len());
+    for (a, b) in buf.iter_mut().zip(key) {
+        *a ^= *b;
+    }
+}
+
+/// Return low coefficients of the lexicographically first irreducible
+/// polynomial of degree `n*8` (without the leading term), as used by CMAC
+/// and PMAC for the given block size `n`.
+///
+/// # Panics
+/// If there is no defined polynomial for the block size.
+fn poly(n: usize) -> &'static [u8] {
+    match n {
+        8 => &[0x1b],
+        16 => &[0x87],
+        32 => &[0x04, 0x25],
+        64 => &[0x01, 0x25],
+        128 => &[0x08, 0x00, 0x43],
+        _ => panic!("unsupported block size"),
+    }
+}
+
+/// Multiply block by `x` in the finite field defined by `poly`.
+pub fn dbl<N: ArrayLength<u8>>(block: &mut GenericArray<u8, N>) {
+    let rb = poly(block.len());
+    let mask = 0u8.wrapping_sub(block[0] >> 7);
+    let mut carry = 0;
+    for b in block.iter_mut().rev() {
+        let t = *b >> 7;
+        *b = (*b << 1) | carry;
+        carry = t;
+    }
+    let offset = block.len() - rb.len();
+    for (a, b) in block[offset..].iter_mut().zip(rb) {
+        *a ^= b & mask;
+    }
+}
+
+/// Multiply block by `x^-1` in the finite field defined by `poly`.
+pub fn halve<N: ArrayLength<u8>>(block: &mut GenericArray<u8, N>) {
+    let rb = poly(block.len());
+    let mask = 0u8.wrapping_sub(block[block.len() - 1] & 1);
+    let offset = block.len() - rb.len();
+    for (a, b) in block[offset..].iter_mut().zip(rb) {
+        *a ^= b & mask;
+    }
+    let mut carry = mask & 0x80;
+    for b in block.iter_mut() {
+        let t = *b << 7;
+        *b = (*b >> 1) | carry;
+        carry = t;
+    }
+}
+
+/// Buffer `data` passing full blocks to `f`, which always receives a slice
+/// with length multiple of the block size.
+///
+/// Last block of the data is always kept in the buffer (even if it's
+/// complete), so it can be processed differently on finalization.
+pub fn buffer_input<N, F>(
+    buffer: &mut GenericArray<u8, N>, pos: &mut usize, data: &[u8], mut f: F,
+)
+    where N: ArrayLength<u8>, F: FnMut(&[u8])
+{
+    let n = buffer.len();
+    let rem = n - *pos;
+    if data.len() <= rem {
+        buffer[*pos..*pos + data.len()].copy_from_slice(data);
+        *pos += data.len();
+        return;
+    }
+    let (head, data) = data.split_at(rem);
+    buffer[*pos..].copy_from_slice(head);
+    f(buffer);
+    // `data` is not empty here, so at least one byte will be buffered
+    let (blocks, tail) = data.split_at((data.len() - 1) / n * n);
+    if !blocks.is_empty() {
+        f(blocks);
+    }
+    buffer[..tail.len()].copy_from_slice(tail);
+    *pos = tail.len();
+}
@@ -0,0 +1,93 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use block_utils::{Block, buffer_input, xor};
+use {InvalidKey, Mac, MacResult};
+
+/// Plain [CBC-MAC][1] generic over block cipher.
+///
+/// Incomplete last block is padded with zeros (ISO/IEC 9797-1 padding
+/// method 1), empty message is processed as a single zero block.
+///
+/// # Warning
+/// **CBC-MAC is insecure for variable-length messages!** Knowing tags of
+/// two messages attacker can forge tag of their concatenation, and zero
+/// padding makes messages which differ only in trailing zero bytes
+/// indistinguishable. Use it only if it's required for compatibility with
+/// existing protocols which authenticate messages of fixed length, in all
+/// other cases use `Cmac` or `Pmac` instead.
+///
+/// [1]: https://en.wikipedia.org/wiki/CBC-MAC
+#[derive(Clone)]
+pub struct InsecureCbcMac<C: BlockEncrypt> {
+    cipher: C,
+    state: Block<C>,
+    buffer: Block<C>,
+    pos: usize,
+}
+
+impl<C: BlockEncrypt> InsecureCbcMac<C> {
+    /// Create new CBC-MAC instance from initialized block cipher.
+    pub fn from_cipher(cipher: C) -> Self {
+        InsecureCbcMac {
+            cipher,
+            state: Default::default(),
+            buffer: Default::default(),
+            pos: 0,
+        }
+    }
+
+    /// Process the last block, leaving the tag in `state`
+    fn finish(&mut self) {
+        for b in self.buffer[self.pos..].iter_mut() { *b = 0; }
+        xor(&mut self.state, &self.buffer);
+        self.cipher.encrypt_block(&mut self.state);
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> Mac for InsecureCbcMac<C> {
+    type OutputSize = C::BlockSize;
+    type KeySize = C::KeySize;
+
+    const MIN_KEY_SIZE: usize = C::MIN_KEY_SIZE;
+    const MAX_KEY_SIZE: usize = C::MAX_KEY_SIZE;
+    const KEY_SIZE_STEP: usize = C::KEY_SIZE_STEP;
+
+    fn new(key: &GenericArray<u8, C::KeySize>) -> Self {
+        Self::from_cipher(C::new(key))
+    }
+
+    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey> {
+        Ok(Self::from_cipher(C::new_varkey(key)?))
+    }
+
+    fn input(&mut self, data: &[u8]) {
+        let InsecureCbcMac {
+            ref cipher, ref mut state, ref mut buffer, ref mut pos
+        } = *self;
+        buffer_input(buffer, pos, data, |blocks| {
+            for block in blocks.chunks(state.len()) {
+                xor(state, block);
+                cipher.encrypt_block(state);
+            }
+        });
+    }
+
+    fn reset(&mut self) {
+        self.state = Default::default();
+        self.pos = 0;
+    }
+
+    fn finalize(mut self) -> MacResult<C::BlockSize> {
+        self.finish();
+        MacResult::new(self.state)
+    }
+
+    fn finalize_reset(&mut self) -> MacResult<C::BlockSize>
+        where Self: Clone
+    {
+        self.finish();
+        let res = MacResult::new(self.state.clone());
+        self.reset();
+        res
+    }
+}
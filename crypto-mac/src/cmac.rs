@@ -0,0 +1,103 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use block_utils::{Block, buffer_input, dbl, xor};
+use {InvalidKey, Mac, MacResult};
+
+/// [Cipher-based Message Authentication Code][1] (CMAC), also known as
+/// OMAC1, generic over block cipher.
+///
+/// Specified in [RFC 4493] and [NIST SP 800-38B]. Supported block sizes
+/// are 64, 128, 256, 512 and 1024 bits, other sizes will result in panic
+/// on initialization.
+///
+/// [1]: https://en.wikipedia.org/wiki/One-key_MAC
+/// [RFC 4493]: https://tools.ietf.org/html/rfc4493
+/// [NIST SP 800-38B]: https://doi.org/10.6028/NIST.SP.800-38B
+#[derive(Clone)]
+pub struct Cmac<C: BlockEncrypt> {
+    cipher: C,
+    key1: Block<C>,
+    key2: Block<C>,
+    state: Block<C>,
+    buffer: Block<C>,
+    pos: usize,
+}
+
+impl<C: BlockEncrypt> Cmac<C> {
+    /// Create new CMAC instance from initialized block cipher.
+    pub fn from_cipher(cipher: C) -> Self {
+        let mut key1 = Block::<C>::default();
+        cipher.encrypt_block(&mut key1);
+        dbl(&mut key1);
+        let mut key2 = key1.clone();
+        dbl(&mut key2);
+
+        Cmac {
+            cipher, key1, key2,
+            state: Default::default(),
+            buffer: Default::default(),
+            pos: 0,
+        }
+    }
+
+    /// Process the last block, leaving the tag in `state`
+    fn finish(&mut self) {
+        let n = self.buffer.len();
+        if self.pos == n {
+            xor(&mut self.buffer, &self.key1);
+        } else {
+            self.buffer[self.pos] = 0x80;
+            for b in self.buffer[self.pos + 1..].iter_mut() { *b = 0; }
+            xor(&mut self.buffer, &self.key2);
+        }
+        xor(&mut self.state, &self.buffer);
+        self.cipher.encrypt_block(&mut self.state);
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> Mac for Cmac<C> {
+    type OutputSize = C::BlockSize;
+    type KeySize = C::KeySize;
+
+    const MIN_KEY_SIZE: usize = C::MIN_KEY_SIZE;
+    const MAX_KEY_SIZE: usize = C::MAX_KEY_SIZE;
+    const KEY_SIZE_STEP: usize = C::KEY_SIZE_STEP;
+
+    fn new(key: &GenericArray<u8, C::KeySize>) -> Self {
+        Self::from_cipher(C::new(key))
+    }
+
+    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey> {
+        Ok(Self::from_cipher(C::new_varkey(key)?))
+    }
+
+    fn input(&mut self, data: &[u8]) {
+        let Cmac { ref cipher, ref mut state, ref mut buffer, ref mut pos, .. }
+            = *self;
+        buffer_input(buffer, pos, data, |blocks| {
+            for block in blocks.chunks(state.len()) {
+                xor(state, block);
+                cipher.encrypt_block(state);
+            }
+        });
+    }
+
+    fn reset(&mut self) {
+        self.state = Default::default();
+        self.pos = 0;
+    }
+
+    fn finalize(mut self) -> MacResult<C::BlockSize> {
+        self.finish();
+        MacResult::new(self.state)
+    }
+
+    fn finalize_reset(&mut self) -> MacResult<C::BlockSize>
+        where Self: Clone
+    {
+        self.finish();
+        let res = MacResult::new(self.state.clone());
+        self.reset();
+        res
+    }
+}
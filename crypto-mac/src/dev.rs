@@ -1,70 +1,293 @@
+use {Mac, MacResult};
 
+/// Test vectors in the format used by `new_test!`
+///
+/// `index` contains little-endian `u16` start and end offsets of key, input
+/// and tag for every test vector.
+#[derive(Copy, Clone, Debug)]
+pub struct TestVectors {
+    pub keys: &'static [u8],
+    pub inputs: &'static [u8],
+    pub tags: &'static [u8],
+    pub index: &'static [u8],
+}
+
+macro_rules! test_vectors {
+    ($test_name:expr) => {
+        TestVectors {
+            keys: include_bytes!(
+                concat!("../tests/data/", $test_name, ".keys.bin")),
+            inputs: include_bytes!(
+                concat!("../tests/data/", $test_name, ".inputs.bin")),
+            tags: include_bytes!(
+                concat!("../tests/data/", $test_name, ".tags.bin")),
+            index: include_bytes!(
+                concat!("../tests/data/", $test_name, ".index.bin")),
+        }
+    }
+}
+
+/// CMAC-AES128 test vectors from NIST SP 800-38B
+pub const CMAC_AES128: TestVectors = test_vectors!("cmac_aes128");
+/// CMAC-AES192 test vectors from NIST SP 800-38B
+pub const CMAC_AES192: TestVectors = test_vectors!("cmac_aes192");
+/// CMAC-AES256 test vectors from NIST SP 800-38B
+pub const CMAC_AES256: TestVectors = test_vectors!("cmac_aes256");
+/// PMAC-AES128 test vectors
+pub const PMAC_AES128: TestVectors = test_vectors!("pmac_aes128");
+/// CBC-MAC-AES128 test vectors
+pub const CBC_MAC_AES128: TestVectors = test_vectors!("cbc_mac_aes128");
+/// HMAC-MD5 test vectors from RFC 2202
+pub const HMAC_MD5: TestVectors = test_vectors!("hmac_md5");
+/// HMAC-SHA1 test vectors from RFC 2202
+pub const HMAC_SHA1: TestVectors = test_vectors!("hmac_sha1");
+/// HMAC-SHA224 test vectors from RFC 4231
+pub const HMAC_SHA224: TestVectors = test_vectors!("hmac_sha224");
+/// HMAC-SHA256 test vectors from RFC 4231
+pub const HMAC_SHA256: TestVectors = test_vectors!("hmac_sha256");
+/// HMAC-SHA384 test vectors from RFC 4231
+pub const HMAC_SHA384: TestVectors = test_vectors!("hmac_sha384");
+/// HMAC-SHA512 test vectors from RFC 4231
+pub const HMAC_SHA512: TestVectors = test_vectors!("hmac_sha512");
+
+/// Check computation of `Mac` using `io` adapters, does nothing if `std`
+/// feature is disabled
+#[cfg(not(feature = "std"))]
+fn run_io_test<M: Mac + Clone>(_mac: &M, _input: &[u8], _tag: &[u8])
+    -> bool
+{
+    true
+}
+
+/// Check computation of `Mac` using `io` adapters
+#[cfg(feature = "std")]
+fn run_io_test<M: Mac + Clone>(mac: &M, input: &[u8], tag: &[u8]) -> bool {
+    use std::io::{self, Write};
+    use MacWriter;
+
+    let mut w = MacWriter::new(mac.clone());
+    let mut r = input;
+    match io::copy(&mut r, &mut w) {
+        Ok(n) if n == input.len() as u64 => (),
+        _ => return false,
+    }
+    if w.into_inner().verify_finalize(tag).is_err() {
+        return false;
+    }
+
+    let mut w = MacWriter::new(mac.clone());
+    for chunk in input.chunks(7) {
+        match w.write(chunk) {
+            Ok(n) if n == chunk.len() => (),
+            _ => return false,
+        }
+    }
+    if w.flush().is_err() || w.into_inner().verify_finalize(tag).is_err() {
+        return false;
+    }
+
+    #[cfg(feature = "digest-std")]
+    {
+        use digest::ReadOptions;
+        use finalize_mac_reader;
+
+        let mut r = input;
+        let options = ReadOptions::new().buffer_size(7);
+        match finalize_mac_reader(mac.clone(), &mut r, options) {
+            Ok(result) if result.is_equal(tag) => (),
+            _ => return false,
+        }
+    }
+
+    run_reader_test(mac, input, tag)
+}
+
+/// Check `MacReader` with the correct and a corrupted tag
+#[cfg(feature = "std")]
+fn run_reader_test<M: Mac + Clone>(mac: &M, input: &[u8], tag: &[u8])
+    -> bool
+{
+    use std::io::{self, Read};
+    use std::vec::Vec;
+    use generic_array::GenericArray;
+    use {MacError, MacReader};
+
+    let mut tag = GenericArray::clone_from_slice(tag);
+    let mut r = MacReader::new(input, mac.clone(), &tag);
+    let mut data = Vec::new();
+    match r.read_to_end(&mut data) {
+        Ok(n) if n == input.len() && data == input => (),
+        _ => return false,
+    }
+    if !r.is_verified() {
+        return false;
+    }
+
+    tag[0] ^= 1;
+    let mut r = MacReader::new(input, mac.clone(), &tag);
+    let err = match r.read_to_end(&mut Vec::new()) {
+        Ok(_) => return false,
+        Err(err) => err,
+    };
+    let is_mac_error = err.get_ref()
+        .map(|e| e.downcast_ref::<MacError>().is_some())
+        .unwrap_or(false);
+    err.kind() == io::ErrorKind::InvalidData && is_mac_error
+        && !r.is_verified()
+}
+
+/// Check computation of `Mac` using async adapters, does nothing if
+/// `digest-async` feature is disabled
+#[doc(hidden)]
+#[cfg(not(feature = "digest-async"))]
+pub fn run_async_test<M: ::Mac + Clone>(_mac: &M, _input: &[u8], _tag: &[u8])
+    -> bool
+{
+    true
+}
+
+#[cfg(feature = "digest-async")]
+pub use self::async_utils::run_async_test;
+
+#[cfg(feature = "digest-async")]
+#[clippy::msrv = "1.36"]
+mod async_utils {
+    use core::pin::Pin;
+    use core::task::Poll;
+    use digest::dev::{TestAsyncReader, block_on, with_context};
+    use futures_io::AsyncWrite;
+    use {Mac, MacWriter, finalize_mac_async_reader};
+
+    /// Check computation of `Mac` using async adapters
+    pub fn run_async_test<M: Mac + Clone>(mac: &M, input: &[u8], tag: &[u8])
+        -> bool
+    {
+        let mut w = MacWriter::new(mac.clone());
+        let written = with_context(|cx| {
+            input.chunks(7).all(|chunk| {
+                match Pin::new(&mut w).poll_write(cx, chunk) {
+                    Poll::Ready(Ok(n)) => n == chunk.len(),
+                    _ => false,
+                }
+            })
+        });
+        if !written || w.into_inner().verify_finalize(tag).is_err() {
+            return false;
+        }
+
+        let mut r = TestAsyncReader::new(input);
+        match block_on(finalize_mac_async_reader(mac.clone(), &mut r)) {
+            Ok(result) => result.is_equal(tag),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Check `Mac` computation for a single test vector
+fn run_test<M: Mac + Clone>(key: &[u8], input: &[u8], tag: &[u8]) -> bool {
+    let mut mac = M::new_varkey(key).unwrap();
+    // test io and async adapters
+    if !run_io_test(&mac, input, tag) || !run_async_test(&mac, input, tag) {
+        return false;
+    }
+    mac.input(input);
+    let result = mac.clone().finalize();
+    if !result.is_equal(tag) {
+        return false;
+    }
+    let result = mac.finalize_reset();
+    if !result.is_equal(tag) {
+        return false;
+    }
+    // test if reset worked correctly
+    mac.input(input);
+    if mac.verify_reset(tag).is_err() {
+        return false;
+    }
+
+    // test writing code into provided buffer
+    let mut code = Default::default();
+    mac.input(input);
+    mac.finalize_into_reset(&mut code);
+    if !MacResult::new(code).is_equal(tag) {
+        return false;
+    }
+    let mut code = Default::default();
+    mac.input(input);
+    mac.clone().finalize_into(&mut code);
+    if !MacResult::new(code).is_equal(tag) {
+        return false;
+    }
+
+    // test explicit reset
+    mac.input(b"garbage");
+    mac.reset();
+
+    // test reading byte by byte
+    for i in 0..input.len() {
+        mac.input(&input[i..i + 1]);
+    }
+    mac.verify_finalize(tag).is_ok()
+}
+
+/// Run `Mac` tests for all vectors in `v`, panics on failure
+pub fn run_test_vectors<M: Mac + Clone>(v: &TestVectors) {
+    // u32 (2 bytes); start + end (x2); key, input, tag (x3)
+    assert_eq!(v.index.len() % (2*3*2), 0, "invlaid index length");
+    for (i, chunk) in v.index.chunks(2*3*2).enumerate() {
+        // read LE `u16` values without alignment assumptions
+        let mut idx = [[0usize; 2]; 3];
+        for (j, b) in chunk.chunks(2).enumerate() {
+            idx[j / 2][j % 2] = (b[0] as usize) | (b[1] as usize) << 8;
+        }
+        let key = &v.keys[idx[0][0]..idx[0][1]];
+        let input = &v.inputs[idx[1][0]..idx[1][1]];
+        let tag = &v.tags[idx[2][0]..idx[2][1]];
+        if !run_test::<M>(key, input, tag) {
+            panic!("\n\
+                Failed test №{}\n\
+                key: [{}..{}]\t{:?}\n\
+                input: [{}..{}]\t{:?}\n\
+                tag: [{}..{}]\t{:?}\n",
+                i, idx[0][0], idx[0][1], key,
+                idx[1][0], idx[1][1], input,
+                idx[2][0], idx[2][1], tag,
+            );
+        }
+    }
+}
+
+/// Define test which runs `Mac` over test vectors stored in the `data`
+/// directory next to the calling test file
 #[macro_export]
 macro_rules! new_test {
     ($name:ident, $test_name:expr, $mac:ty) => {
         #[test]
         fn $name() {
-            use crypto_mac::Mac;
-
-            fn run_test(key: &[u8], input: &[u8], tag: &[u8]) -> bool {
-                let mut mac = <$mac as Mac>::new_varkey(key).unwrap();
-                mac.input(input);
-                let result = mac.result();
-                if !result.is_equal(tag) {
-                    return false;
-                }
-                // test if reset worked correctly
-                mac.input(input);
-                if mac.verify(&tag).is_err() {
-                    return false;
-                }
-
-                // test reading byte by byte
-                for i in 0..input.len() {
-                    mac.input(&input[i..i + 1]);
-                }
-                mac.verify(tag).unwrap();
-                true
-            }
-
-            let keys = include_bytes!(
-                concat!("data/", $test_name, ".keys.bin"));
-            let inputs = include_bytes!(
-                concat!("data/", $test_name, ".inputs.bin"));
-            let tags = include_bytes!(
-                concat!("data/", $test_name, ".tags.bin"));
-            let index = include_bytes!(
-                concat!("data/", $test_name, ".index.bin"));
-
-            // u32 (2 bytes); start + end (x2); key, input, tag (x3)
-            assert_eq!(index.len() % (2*3*2), 0, "invlaid index length");
-            for (i, chunk) in index.chunks(2*3*2).enumerate() {
-                // proper aligment is assumed here
-                let mut idx = unsafe {
-                    *(chunk.as_ptr() as *const [[u16; 2]; 3])
-                };
-                // convert to LE for BE machine
-                for val in idx.iter_mut() {
-                    for i in val.iter_mut() { *i = i.to_le(); }
-                }
-                let key = &keys[(idx[0][0] as usize)..(idx[0][1] as usize)];
-                let input = &inputs[
-                    (idx[1][0] as usize)..(idx[1][1] as usize)];
-                let tag = &tags[
-                    (idx[2][0] as usize)..(idx[2][1] as usize)];
-                if !run_test(key, input, tag) {
-                    panic!("\n\
-                        Failed test №{}\n\
-                        key: [{}..{}]\t{:?}\n\
-                        input: [{}..{}]\t{:?}\n\
-                        tag: [{}..{}]\t{:?}\n",
-                        i, idx[0][0], idx[0][1], key,
-                        idx[1][0], idx[1][1], input,
-                        idx[2][0], idx[2][1], tag,
-                    );
-                }
-            }
+            let vectors = crypto_mac::dev::TestVectors {
+                keys: include_bytes!(
+                    concat!("data/", $test_name, ".keys.bin")),
+                inputs: include_bytes!(
+                    concat!("data/", $test_name, ".inputs.bin")),
+                tags: include_bytes!(
+                    concat!("data/", $test_name, ".tags.bin")),
+                index: include_bytes!(
+                    concat!("data/", $test_name, ".index.bin")),
+            };
+            crypto_mac::dev::run_test_vectors::<$mac>(&vectors);
+        }
+    }
+}
 
+/// Define test which runs `Mac` over test vectors exposed by this module,
+/// e.g. `new_vectors_test!(cmac_aes128, Cmac<Aes128>, CMAC_AES128)`
+#[macro_export]
+macro_rules! new_vectors_test {
+    ($name:ident, $mac:ty, $vectors:ident) => {
+        #[test]
+        fn $name() {
+            crypto_mac::dev::run_test_vectors::<$mac>(
+                &crypto_mac::dev::$vectors);
         }
     }
 }
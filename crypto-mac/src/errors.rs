@@ -1,15 +1,42 @@
 use core::fmt;
 #[cfg(feature = "std")]
 use std::error;
+#[cfg(feature = "block-cipher-trait")]
+use block_cipher_trait;
 
 /// Error type for signaling failed MAC verification
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 pub struct MacError;
 
-/// Error type for signaling invalid key length for MAC initialization
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
-pub struct InvalidKeyLength;
+/// Error type for signaling invalid key for MAC initialization
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InvalidKey {
+    /// Key has length which is not accepted by the MAC
+    Length {
+        /// Length of the rejected key in bytes
+        len: usize,
+        /// Minimum accepted key length in bytes
+        min: usize,
+        /// Maximum accepted key length in bytes
+        max: usize,
+        /// Step between accepted key lengths in bytes
+        step: usize,
+    },
+    /// Key is known to be weak and was rejected by the MAC
+    Weak,
+}
 
+#[cfg(feature = "block-cipher-trait")]
+impl From<block_cipher_trait::InvalidKey> for InvalidKey {
+    fn from(err: block_cipher_trait::InvalidKey) -> InvalidKey {
+        use block_cipher_trait::InvalidKey as CipherInvalidKey;
+        match err {
+            CipherInvalidKey::Length { len, min, max, step } =>
+                InvalidKey::Length { len, min, max, step },
+            CipherInvalidKey::Weak => InvalidKey::Weak,
+        }
+    }
+}
 
 impl fmt::Display for MacError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -17,9 +44,21 @@ impl fmt::Display for MacError {
     }
 }
 
-impl fmt::Display for InvalidKeyLength {
+impl fmt::Display for InvalidKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("invalid key length")
+        match *self {
+            InvalidKey::Length { len, min, max, step } => {
+                write!(f, "invalid key length {}, ", len)?;
+                if min == max {
+                    write!(f, "expected {}", min)
+                } else if step == 1 {
+                    write!(f, "expected {}..={}", min, max)
+                } else {
+                    write!(f, "expected {}..={} with step {}", min, max, step)
+                }
+            },
+            InvalidKey::Weak => f.write_str("weak key"),
+        }
     }
 }
 
@@ -31,8 +70,11 @@ impl error::Error for MacError {
 }
 
 #[cfg(feature = "std")]
-impl error::Error for InvalidKeyLength {
+impl error::Error for InvalidKey {
     fn description(&self) -> &str {
-        "invalid key length"
+        match *self {
+            InvalidKey::Length { .. } => "invalid key length",
+            InvalidKey::Weak => "weak key",
+        }
     }
-}
\ No newline at end of file
+}
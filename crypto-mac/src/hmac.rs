@@ -0,0 +1,94 @@
+use digest::{BlockInput, FixedOutput, Input};
+use digest::generic_array::GenericArray;
+use digest::generic_array::typenum::Unsigned;
+use core::mem;
+use {InvalidKey, Mac, MacResult};
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5C;
+
+/// Generic implementation of [HMAC][1] over hash functions which implement
+/// traits from the `digest` crate.
+///
+/// Hasher states after processing of inner and outer padded keys are cached,
+/// so resetting of `Hmac` instance is cheap.
+///
+/// [1]: https://tools.ietf.org/html/rfc2104
+#[derive(Clone)]
+pub struct Hmac<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    digest: D,
+    inner_init: D,
+    outer_init: D,
+}
+
+impl<D> Mac for Hmac<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    type OutputSize = D::OutputSize;
+    type KeySize = D::BlockSize;
+
+    const MIN_KEY_SIZE: usize = 0;
+    const MAX_KEY_SIZE: usize = !0;
+
+    fn new(key: &GenericArray<u8, D::BlockSize>) -> Self {
+        Self::new_varkey(key.as_slice()).unwrap()
+    }
+
+    /// Create new HMAC instance, keys longer than hash function block size
+    /// are hashed.
+    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey> {
+        let mut padded_key = GenericArray::<u8, D::BlockSize>::default();
+        if key.len() <= D::BlockSize::to_usize() {
+            padded_key[..key.len()].copy_from_slice(key);
+        } else {
+            let mut digest = D::default();
+            digest.process(key);
+            let hash = digest.finalize_fixed();
+            let n = hash.len();
+            debug_assert!(n <= padded_key.len());
+            padded_key[..n].copy_from_slice(&hash);
+        }
+
+        let mut inner_init = D::default();
+        let mut outer_init = D::default();
+        let mut buf = padded_key.clone();
+        for b in buf.iter_mut() { *b ^= IPAD; }
+        inner_init.process(&buf);
+        for (b, k) in buf.iter_mut().zip(padded_key.iter()) {
+            *b = k ^ OPAD;
+        }
+        outer_init.process(&buf);
+
+        Ok(Hmac { digest: inner_init.clone(), inner_init, outer_init })
+    }
+
+    #[inline]
+    fn input(&mut self, data: &[u8]) {
+        self.digest.process(data);
+    }
+
+    fn reset(&mut self) {
+        self.digest = self.inner_init.clone();
+    }
+
+    fn finalize(self) -> MacResult<D::OutputSize> {
+        let mut outer = self.outer_init;
+        outer.process(&self.digest.finalize_fixed());
+        MacResult::new(outer.finalize_fixed())
+    }
+
+    fn finalize_into(self, out: &mut GenericArray<u8, D::OutputSize>) {
+        let mut outer = self.outer_init;
+        outer.process(&self.digest.finalize_fixed());
+        outer.finalize_into(out);
+    }
+
+    fn finalize_reset(&mut self) -> MacResult<D::OutputSize> {
+        let digest = mem::replace(&mut self.digest, self.inner_init.clone());
+        let mut outer = self.outer_init.clone();
+        outer.process(&digest.finalize_fixed());
+        MacResult::new(outer.finalize_fixed())
+    }
+}
@@ -1,57 +1,227 @@
 //! This crate provides trait for Message Authentication Code (MAC) algorithms.
+//!
+//! With enabled `block-cipher-trait` feature it also provides generic
+//! implementations of block cipher based MACs: `Cmac`, `Pmac` and
+//! `InsecureCbcMac`. With enabled `digest` feature it provides `Hmac`
+//! generic over hash functions from the `digest` crate. With enabled `std`
+//! feature `MacWriter` adapter implements `io::Write` for `Mac` instances
+//! and `MacReader` verifies tag of data read from `io::Read`. With enabled
+//! `digest-std` feature `finalize_mac_reader` computes `Mac` of all data
+//! from `io::Read` using `digest::ReadOptions`.
+//!
+//! With enabled `async` feature `MacWriter` implements
+//! `futures_io::AsyncWrite`. With enabled `digest-async` feature
+//! `finalize_mac_async_reader` computes `Mac` of all data from
+//! `futures_io::AsyncRead` without blocking. These features require Rust
+//! 1.36 or later.
+//!
+//! With enabled `dev` feature test vectors for the provided MACs are
+//! exposed as constants in the `dev` module and can be checked using the
+//! `new_vectors_test!` macro.
 #![no_std]
 extern crate constant_time_eq;
 pub extern crate generic_array;
+#[cfg(feature = "block-cipher-trait")]
+pub extern crate block_cipher_trait;
+#[cfg(feature = "digest")]
+pub extern crate digest;
 
 #[cfg(feature = "std")]
 extern crate std;
+#[cfg(feature = "async")]
+pub extern crate futures_io;
 
 use constant_time_eq::constant_time_eq;
 use generic_array::{GenericArray, ArrayLength};
 use generic_array::typenum::Unsigned;
 
 mod errors;
+#[cfg(feature = "block-cipher-trait")]
+mod block_utils;
+#[cfg(feature = "block-cipher-trait")]
+mod cmac;
+#[cfg(feature = "block-cipher-trait")]
+mod pmac;
+#[cfg(feature = "block-cipher-trait")]
+mod cbc_mac;
+#[cfg(feature = "digest")]
+mod hmac;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod writer;
+#[cfg(feature = "async")]
+#[clippy::msrv = "1.36"]
+mod async_io;
 #[cfg(feature = "dev")]
 pub mod dev;
 
-pub use errors::{InvalidKeyLength, MacError};
+#[allow(deprecated)]
+pub use errors::{InvalidKey, MacError};
+#[cfg(feature = "block-cipher-trait")]
+pub use cmac::Cmac;
+#[cfg(feature = "block-cipher-trait")]
+pub use pmac::Pmac;
+#[cfg(feature = "block-cipher-trait")]
+pub use cbc_mac::InsecureCbcMac;
+#[cfg(feature = "digest")]
+pub use hmac::Hmac;
+#[cfg(feature = "std")]
+pub use reader::MacReader;
+#[cfg(feature = "digest-std")]
+pub use reader::finalize_mac_reader;
+#[cfg(feature = "digest-async")]
+pub use async_io::{FinalizeMacAsyncReader, finalize_mac_async_reader};
+#[cfg(feature = "std")]
+pub use writer::MacWriter;
 
 /// The `Mac` trait defines methods for a Message Authentication algorithm.
 pub trait Mac: core::marker::Sized {
     type OutputSize: ArrayLength<u8>;
     type KeySize: ArrayLength<u8>;
 
+    /// Minimum key size in bytes accepted by `new_varkey`
+    const MIN_KEY_SIZE: usize = Self::KeySize::USIZE;
+    /// Maximum key size in bytes accepted by `new_varkey`
+    const MAX_KEY_SIZE: usize = Self::KeySize::USIZE;
+    /// Step between key sizes in bytes accepted by `new_varkey`, zero
+    /// means that only `MIN_KEY_SIZE` is accepted
+    const KEY_SIZE_STEP: usize = 1;
+
     /// Create new MAC instance from key with fixed size.
     fn new(key: &GenericArray<u8, Self::KeySize>) -> Self;
 
     /// Create new MAC instance from key with variable size.
     ///
     /// Default implementation will accept only keys with length equal to
-    /// `KeySize`, but some MACs can accept range of key lengths.
-    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKeyLength> {
+    /// `KeySize`, but some MACs can accept range of key lengths. Such MACs
+    /// must override key size constants and this method, which is checked
+    /// in debug builds.
+    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey> {
+        debug_assert!(
+            Self::MIN_KEY_SIZE == Self::KeySize::USIZE
+                && Self::MAX_KEY_SIZE == Self::KeySize::USIZE,
+            "MACs with key size range must override `new_varkey`"
+        );
+        Self::check_key_size(key.len())?;
         if key.len() != Self::KeySize::to_usize() {
-            Err(InvalidKeyLength)
+            return Err(Self::invalid_key_length(key.len()));
+        }
+        Ok(Self::new(GenericArray::from_slice(key)))
+    }
+
+    /// Check if key length `len` lies in the range described by the key
+    /// size constants.
+    fn check_key_size(len: usize) -> Result<(), InvalidKey> {
+        let (min, max) = (Self::MIN_KEY_SIZE, Self::MAX_KEY_SIZE);
+        let valid = len >= min && len <= max && match Self::KEY_SIZE_STEP {
+            0 => len == min,
+            step => (len - min) % step == 0,
+        };
+        if valid {
+            Ok(())
         } else {
-            Ok(Self::new(GenericArray::from_slice(key)))
+            Err(Self::invalid_key_length(len))
+        }
+    }
+
+    /// Create `InvalidKey::Length` error for key length `len` using the key
+    /// size constants.
+    fn invalid_key_length(len: usize) -> InvalidKey {
+        InvalidKey::Length {
+            len,
+            min: Self::MIN_KEY_SIZE,
+            max: Self::MAX_KEY_SIZE,
+            step: Self::KEY_SIZE_STEP,
         }
     }
 
     /// Process input data.
     fn input(&mut self, data: &[u8]);
 
+    /// Reset `Mac` instance to the state right after its initialization
+    /// with key.
+    fn reset(&mut self);
+
+    /// Obtain the result of a `Mac` computation as a `MacResult` and consume
+    /// `Mac` instance.
+    fn finalize(self) -> MacResult<Self::OutputSize>;
+
+    /// Obtain the result of a `Mac` computation as a `MacResult` and reset
+    /// `Mac` instance.
+    ///
+    /// Default implementation finalizes a copy of the `Mac` state,
+    /// implementors are encouraged to override it with a more efficient
+    /// variant.
+    fn finalize_reset(&mut self) -> MacResult<Self::OutputSize>
+        where Self: Clone
+    {
+        let res = self.clone().finalize();
+        self.reset();
+        res
+    }
+
     /// Obtain the result of a `Mac` computation as a `MacResult` and reset
     /// `Mac` instance.
-    fn result(&mut self) -> MacResult<Self::OutputSize>;
+    #[deprecated(note = "use `finalize_reset` instead")]
+    fn result(&mut self) -> MacResult<Self::OutputSize>
+        where Self: Clone
+    {
+        self.finalize_reset()
+    }
+
+    /// Write the code value into `out` and consume `Mac` instance.
+    ///
+    /// Be very careful using this method, since incorrect use of the code
+    /// value may permit timing attacks which defeat the security provided by
+    /// the `Mac` trait. Default implementation delegates to `finalize`.
+    fn finalize_into(self, out: &mut GenericArray<u8, Self::OutputSize>) {
+        *out = self.finalize().code();
+    }
+
+    /// Write the code value into `out` and reset `Mac` instance.
+    ///
+    /// The same considerations as for `finalize_into` apply. Default
+    /// implementation delegates to `finalize_reset`.
+    fn finalize_into_reset(
+        &mut self, out: &mut GenericArray<u8, Self::OutputSize>,
+    )
+        where Self: Clone
+    {
+        *out = self.finalize_reset().code();
+    }
+
+    /// Check if code is correct for the processed input and consume
+    /// `Mac` instance.
+    fn verify_finalize(self, code: &[u8]) -> Result<(), MacError> {
+        check_code(self.finalize(), code)
+    }
 
     /// Check if code is correct for the processed input and reset
     /// `Mac` instance.
-    fn verify(&mut self, code: &[u8]) -> Result<(), MacError> {
-        let result = self.result();
-        if result.is_equal(code) {
-            Ok(())
-        } else {
-            Err(MacError)
-        }
+    fn verify_reset(&mut self, code: &[u8]) -> Result<(), MacError>
+        where Self: Clone
+    {
+        check_code(self.finalize_reset(), code)
+    }
+
+    /// Check if code is correct for the processed input and reset
+    /// `Mac` instance.
+    #[deprecated(note = "use `verify_reset` instead")]
+    fn verify(&mut self, code: &[u8]) -> Result<(), MacError>
+        where Self: Clone
+    {
+        self.verify_reset(code)
+    }
+}
+
+fn check_code<N>(result: MacResult<N>, code: &[u8]) -> Result<(), MacError>
+    where N: ArrayLength<u8>
+{
+    if result.is_equal(code) {
+        Ok(())
+    } else {
+        Err(MacError)
     }
 }
 
@@ -0,0 +1,163 @@
+use block_cipher_trait::{BlockEncrypt, NewBlockCipher};
+use block_cipher_trait::generic_array::GenericArray;
+use block_cipher_trait::generic_array::typenum::{U32, Unsigned};
+use block_utils::{Block, ParBlocks, buffer_input, dbl, halve, xor};
+use {InvalidKey, Mac, MacResult};
+
+/// Number of precomputed `L(i)` values, it's enough for messages up to
+/// 2^32 blocks long, for longer messages values will be computed on the fly.
+type LCacheSize = U32;
+
+/// [Parallelizable Message Authentication Code][1] (PMAC) generic over
+/// block cipher.
+///
+/// This is PMAC1 as defined by Rogaway in "Efficient Instantiations of
+/// Tweakable Blockciphers and Refinements to Modes OCB and PMAC". Unlike
+/// CMAC, blocks are processed independently, so `ParBlocks` blocks are
+/// encrypted at once. Supported block sizes are the same as for `Cmac`.
+///
+/// [1]: https://web.cs.ucdavis.edu/~rogaway/ocb/pmac.htm
+#[derive(Clone)]
+pub struct Pmac<C: BlockEncrypt> {
+    cipher: C,
+    /// `L(i) = L * x^i` values
+    l_cache: GenericArray<Block<C>, LCacheSize>,
+    /// `L(-1) = L * x^-1` value
+    l_inv: Block<C>,
+    offset: Block<C>,
+    sigma: Block<C>,
+    buffer: Block<C>,
+    pos: usize,
+    /// Number of processed blocks
+    counter: u64,
+}
+
+/// Get `L(ntz(i))` value, where `i` is a number of the block.
+fn l_value<C>(l_cache: &GenericArray<Block<C>, LCacheSize>, i: u64)
+    -> Block<C>
+    where C: BlockEncrypt
+{
+    let ntz = i.trailing_zeros() as usize;
+    let last = LCacheSize::to_usize() - 1;
+    if ntz <= last {
+        return l_cache[ntz].clone();
+    }
+    let mut l = l_cache[last].clone();
+    for _ in last..ntz {
+        dbl(&mut l);
+    }
+    l
+}
+
+impl<C: BlockEncrypt> Pmac<C> {
+    /// Create new PMAC instance from initialized block cipher.
+    pub fn from_cipher(cipher: C) -> Self {
+        let mut l = Block::<C>::default();
+        cipher.encrypt_block(&mut l);
+
+        let mut l_inv = l.clone();
+        halve(&mut l_inv);
+
+        let mut l_cache = GenericArray::<Block<C>, LCacheSize>::default();
+        for v in l_cache.iter_mut() {
+            *v = l.clone();
+            dbl(&mut l);
+        }
+
+        Pmac {
+            cipher, l_cache, l_inv,
+            offset: Default::default(),
+            sigma: Default::default(),
+            buffer: Default::default(),
+            pos: 0,
+            counter: 0,
+        }
+    }
+
+    /// Process the last block, leaving the tag in `sigma`
+    fn finish(&mut self) {
+        let n = self.buffer.len();
+        if self.pos == n {
+            xor(&mut self.sigma, &self.buffer);
+            xor(&mut self.sigma, &self.l_inv);
+        } else {
+            self.buffer[self.pos] = 0x80;
+            for b in self.buffer[self.pos + 1..].iter_mut() { *b = 0; }
+            xor(&mut self.sigma, &self.buffer);
+        }
+        self.cipher.encrypt_block(&mut self.sigma);
+    }
+}
+
+impl<C: NewBlockCipher + BlockEncrypt> Mac for Pmac<C> {
+    type OutputSize = C::BlockSize;
+    type KeySize = C::KeySize;
+
+    const MIN_KEY_SIZE: usize = C::MIN_KEY_SIZE;
+    const MAX_KEY_SIZE: usize = C::MAX_KEY_SIZE;
+    const KEY_SIZE_STEP: usize = C::KEY_SIZE_STEP;
+
+    fn new(key: &GenericArray<u8, C::KeySize>) -> Self {
+        Self::from_cipher(C::new(key))
+    }
+
+    fn new_varkey(key: &[u8]) -> Result<Self, InvalidKey> {
+        Ok(Self::from_cipher(C::new_varkey(key)?))
+    }
+
+    fn input(&mut self, data: &[u8]) {
+        let Pmac {
+            ref cipher, ref l_cache, ref mut offset, ref mut sigma,
+            ref mut buffer, ref mut pos, ref mut counter, ..
+        } = *self;
+        let n = C::BlockSize::to_usize();
+        let pb = C::ParBlocks::to_usize();
+        buffer_input(buffer, pos, data, |blocks| {
+            for chunk in blocks.chunks(n*pb) {
+                if chunk.len() == n*pb {
+                    let mut par_blocks = ParBlocks::<C>::default();
+                    for (b, m) in par_blocks.iter_mut().zip(chunk.chunks(n)) {
+                        *counter += 1;
+                        xor(offset, &l_value::<C>(l_cache, *counter));
+                        b.copy_from_slice(m);
+                        xor(b, offset);
+                    }
+                    cipher.encrypt_blocks(&mut par_blocks);
+                    for b in par_blocks.iter() {
+                        xor(sigma, b);
+                    }
+                } else {
+                    for m in chunk.chunks(n) {
+                        *counter += 1;
+                        xor(offset, &l_value::<C>(l_cache, *counter));
+                        let mut b = Block::<C>::clone_from_slice(m);
+                        xor(&mut b, offset);
+                        cipher.encrypt_block(&mut b);
+                        xor(sigma, &b);
+                    }
+                }
+            }
+        });
+    }
+
+    fn reset(&mut self) {
+        self.offset = Default::default();
+        self.sigma = Default::default();
+        self.pos = 0;
+        self.counter = 0;
+    }
+
+    fn finalize(mut self) -> MacResult<C::BlockSize> {
+        self.finish();
+        MacResult::new(self.sigma)
+    }
+
+    fn finalize_reset(&mut self) -> MacResult<C::BlockSize>
+        where Self: Clone
+    {
+        self.finish();
+        let res = MacResult::new(self.sigma.clone());
+        self.reset();
+        res
+    }
+}
@@ -0,0 +1,123 @@
+use generic_array::GenericArray;
+use std::io;
+use {Mac, MacError};
+#[cfg(feature = "digest-std")]
+use MacResult;
+#[cfg(feature = "digest-std")]
+use digest::{Input, ReadOptions, process_reader};
+
+/// Reader adapter which feeds all data read from the wrapped reader into
+/// `Mac` and verifies the expected tag on reaching the end of the stream.
+///
+/// If verification fails, read which reached the end of the stream (and
+/// all subsequent reads) returns `io::Error` of the `InvalidData` kind
+/// carrying `MacError`.
+///
+/// # Security
+/// Data returned by the reader is unauthenticated until the end of the
+/// stream is successfully reached, i.e. `read` returned `Ok(0)` for
+/// a non-empty buffer (`read_to_end` and `io::copy` do it automatically).
+/// It must not be acted upon before that.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use std::io::{self, Read};
+/// use crypto_mac::{Mac, MacReader};
+///
+/// let mac = HmacSha256::new_varkey(key)?;
+/// let mut reader = MacReader::new(file, mac, &tag);
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data)?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct MacReader<R: io::Read, M: Mac> {
+    reader: R,
+    mac: Option<M>,
+    tag: GenericArray<u8, M::OutputSize>,
+    verified: bool,
+}
+
+impl<R: io::Read, M: Mac> MacReader<R, M> {
+    /// Create new adapter from reader, `Mac` instance and expected tag
+    pub fn new(reader: R, mac: M, tag: &GenericArray<u8, M::OutputSize>)
+        -> Self
+    {
+        MacReader { reader, mac: Some(mac), tag: tag.clone(), verified: false }
+    }
+
+    /// Get reference to the wrapped reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Check if the end of the stream was reached and the tag was
+    /// successfully verified
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+
+    /// Unwrap the adapter, returning the wrapped reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: io::Read, M: Mac> io::Read for MacReader<R, M> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.verified {
+            return Ok(0);
+        }
+        let mut mac = match self.mac.take() {
+            Some(mac) => mac,
+            None => return Err(mac_error()),
+        };
+        let n = match self.reader.read(buf) {
+            Ok(n) => n,
+            Err(err) => {
+                self.mac = Some(mac);
+                return Err(err);
+            },
+        };
+        if n == 0 && !buf.is_empty() {
+            mac.verify_finalize(&self.tag).map_err(|_| mac_error())?;
+            self.verified = true;
+        } else {
+            mac.input(&buf[..n]);
+            self.mac = Some(mac);
+        }
+        Ok(n)
+    }
+}
+
+fn mac_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, MacError)
+}
+
+/// Feed all data from `reader` into `mac` and return the result of
+/// the `Mac` computation.
+///
+/// Buffer size and progress reporting are controlled by `options` in the
+/// same way as for `digest::process_reader`. Available only with enabled
+/// `digest-std` feature.
+#[cfg(feature = "digest-std")]
+pub fn finalize_mac_reader<M, R>(mac: M, reader: &mut R,
+    options: ReadOptions) -> io::Result<MacResult<M::OutputSize>>
+    where M: Mac, R: io::Read + ?Sized
+{
+    let mut input = MacInput(mac);
+    process_reader(&mut input, reader, options)?;
+    Ok(input.0.finalize())
+}
+
+/// Adapter which feeds data processed by `digest::Input` into `Mac`
+#[cfg(feature = "digest-std")]
+#[derive(Debug)]
+pub(crate) struct MacInput<M: Mac>(pub(crate) M);
+
+#[cfg(feature = "digest-std")]
+impl<M: Mac> Input for MacInput<M> {
+    fn process(&mut self, input: &[u8]) {
+        self.0.input(input);
+    }
+}
@@ -0,0 +1,58 @@
+use std::io;
+use Mac;
+
+/// Adapter which implements `io::Write` by forwarding written data to
+/// `Mac::input`.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use std::io;
+/// use crypto_mac::{Mac, MacWriter};
+///
+/// let mut writer = MacWriter::new(HmacSha256::new_varkey(key)?);
+/// io::copy(&mut file, &mut writer)?;
+/// writer.into_inner().verify_finalize(tag)?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MacWriter<M: Mac> {
+    mac: M,
+}
+
+impl<M: Mac> MacWriter<M> {
+    /// Create new adapter wrapping `mac`
+    pub fn new(mac: M) -> Self {
+        MacWriter { mac }
+    }
+
+    /// Get reference to the wrapped `Mac` instance
+    pub fn get_ref(&self) -> &M {
+        &self.mac
+    }
+
+    /// Get mutable reference to the wrapped `Mac` instance
+    pub fn get_mut(&mut self) -> &mut M {
+        &mut self.mac
+    }
+
+    /// Unwrap the adapter, returning the wrapped `Mac` instance
+    pub fn into_inner(self) -> M {
+        self.mac
+    }
+}
+
+impl<M: Mac> io::Write for MacWriter<M> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.mac.input(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.mac.input(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Digest, Input};
+use read::DEFAULT_BUFFER_SIZE;
+use generic_array::GenericArray;
+use core::future::Future;
+use core::iter;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_io::{AsyncRead, AsyncWrite};
+use std::io;
+use std::vec::Vec;
+use writer::InputWriter;
+
+/// Future returned by `process_async_reader`.
+///
+/// Resolves to the hasher after all data from the reader was fed into it.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct ProcessAsyncReader<'a, D, R: ?Sized + 'a> {
+    hasher: Option<D>,
+    reader: &'a mut R,
+    buffer: Vec<u8>,
+}
+
+// Neither hasher nor reader are ever pinned
+impl<'a, D, R: ?Sized> Unpin for ProcessAsyncReader<'a, D, R> {}
+
+/// Feed all data from `reader` into `hasher` without blocking.
+///
+/// To keep ownership of the hasher pass a mutable reference to it. Reads
+/// interrupted by signals are retried, other errors are returned
+/// immediately. Data is read using buffer of `DEFAULT_BUFFER_SIZE` bytes.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use digest::{Digest, process_async_reader};
+///
+/// let mut hasher = sha2::Sha256::new();
+/// process_async_reader(&mut hasher, &mut stream).await?;
+/// let result = hasher.finalize();
+/// ```
+pub fn process_async_reader<'a, D, R>(hasher: D, reader: &'a mut R)
+    -> ProcessAsyncReader<'a, D, R>
+    where D: Input, R: AsyncRead + Unpin + ?Sized
+{
+    ProcessAsyncReader {
+        hasher: Some(hasher),
+        reader,
+        buffer: iter::repeat(0).take(DEFAULT_BUFFER_SIZE).collect(),
+    }
+}
+
+impl<'a, D, R> Future for ProcessAsyncReader<'a, D, R>
+    where D: Input, R: AsyncRead + Unpin + ?Sized
+{
+    type Output = io::Result<D>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+        assert!(this.hasher.is_some(), "future polled after completion");
+        loop {
+            let n = match Pin::new(&mut *this.reader)
+                .poll_read(cx, &mut this.buffer)
+            {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Ok(0)) => break,
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(ref e))
+                    if e.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            };
+            if let Some(ref mut hasher) = this.hasher {
+                hasher.process(&this.buffer[..n]);
+            }
+        }
+        Poll::Ready(Ok(this.hasher.take().unwrap()))
+    }
+}
+
+/// Future returned by `Digest::digest_async_reader`.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct DigestAsyncReader<'a, D, R: ?Sized + 'a> {
+    inner: ProcessAsyncReader<'a, D, R>,
+}
+
+impl<'a, D, R> DigestAsyncReader<'a, D, R>
+    where D: Digest, R: AsyncRead + Unpin + ?Sized
+{
+    pub(crate) fn new(reader: &'a mut R) -> Self {
+        DigestAsyncReader { inner: process_async_reader(D::default(), reader) }
+    }
+}
+
+impl<'a, D, R> Future for DigestAsyncReader<'a, D, R>
+    where D: Digest, R: AsyncRead + Unpin + ?Sized
+{
+    type Output = io::Result<GenericArray<u8, D::OutputSize>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match Pin::new(&mut self.inner).poll(cx) {
+            Poll::Ready(Ok(hasher)) => Poll::Ready(Ok(hasher.finalize())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+// Wrapped hasher is never pinned
+impl<D: Input> Unpin for InputWriter<D> {}
+
+impl<D: Input> AsyncWrite for InputWriter<D> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context, buf: &[u8])
+        -> Poll<io::Result<usize>>
+    {
+        self.get_mut().get_mut().process(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context)
+        -> Poll<io::Result<()>>
+    {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context)
+        -> Poll<io::Result<()>>
+    {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -1,5 +1,15 @@
 use super::{Digest, Input, VariableOutput, ExtendableOutput, XofReader};
-use core::fmt::Debug;
+use super::{InputWriter, Reset, SerializableState};
+use core::fmt::{self, Debug};
+use core::str;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(feature = "std")]
+use super::{HashingReader, HashingWriter, ReadOptions};
+#[cfg(feature = "std")]
+use super::{finalize_variable_reader, finalize_xof_reader};
 
 pub struct Test {
     pub name: &'static str,
@@ -21,13 +31,13 @@ macro_rules! new_tests {
     [ $( $name:expr ),+, ] => (new_tests![$($name),+])
 }
 
-pub fn run_digest_tests<D: Digest + Debug + Clone>(tests: &[Test]) {
+pub fn run_digest_tests<D: Digest + Reset + Debug + Clone>(tests: &[Test]) {
     // Test that it works when accepting the message all at once
     for t in tests.iter() {
         let mut sh = D::default();
         sh.input(t.input);
 
-        let out = sh.result();
+        let out = sh.finalize();
 
         assert_eq!(out[..], t.output[..]);
     }
@@ -43,14 +53,83 @@ pub fn run_digest_tests<D: Digest + Debug + Clone>(tests: &[Test]) {
             left = left - take;
         }
 
-        let out = sh.result();
+        let out = sh.finalize_reset();
 
         assert_eq!(out[..], t.output[..]);
+
+        // Test that hasher was reset
+        sh.input(t.input);
+        let out = sh.finalize_reset();
+        assert_eq!(out[..], t.output[..]);
+
+        // Test explicit reset
+        sh.input(b"garbage");
+        sh.reset();
+        sh.input(t.input);
+        let out = sh.finalize();
+        assert_eq!(out[..], t.output[..]);
+
+        // Test feeding data through writer adapters
+        if let Ok(s) = str::from_utf8(t.input) {
+            let mut w = InputWriter::new(D::default());
+            fmt::Write::write_str(&mut w, s).unwrap();
+            assert_eq!(w.into_inner().finalize()[..], t.output[..]);
+        }
+        #[cfg(feature = "std")]
+        {
+            let mut sh = D::default();
+            io::Write::write_all(&mut InputWriter::new(&mut sh), t.input)
+                .unwrap();
+            assert_eq!(sh.finalize()[..], t.output[..]);
+
+            let mut data = Vec::new();
+            let mut r = HashingReader::new(t.input, D::default());
+            io::Read::read_to_end(&mut r, &mut data).unwrap();
+            assert_eq!(&data[..], t.input);
+            assert_eq!(r.into_inner().1.finalize()[..], t.output[..]);
+
+            let mut w = HashingWriter::new(Vec::new(), D::default());
+            io::Write::write_all(&mut w, t.input).unwrap();
+            let (data, sh) = w.into_inner();
+            assert_eq!(&data[..], t.input);
+            assert_eq!(sh.finalize()[..], t.output[..]);
+
+            let mut r = t.input;
+            assert_eq!(D::digest_reader(&mut r).unwrap()[..], t.output[..]);
+
+            // Test reading with small buffer and progress reporting
+            let mut total = 0;
+            {
+                let mut report = |n| total = n;
+                let options = ReadOptions::new()
+                    .buffer_size(7)
+                    .progress(&mut report);
+                let out = D::digest_reader_with(&mut &t.input[..], options)
+                    .unwrap();
+                assert_eq!(out[..], t.output[..]);
+            }
+            assert_eq!(total, t.input.len() as u64);
+
+            file_utils::run_file_test::<D>(t);
+        }
+        #[cfg(feature = "async")]
+        async_utils::run_async_test::<D>(t);
+
+        // Test writing result into provided buffer
+        let mut sh = D::default();
+        let mut out = Default::default();
+        sh.input(t.input);
+        sh.finalize_into_reset(&mut out);
+        assert_eq!(out[..], t.output[..]);
+        let mut out = Default::default();
+        sh.input(t.input);
+        sh.finalize_into(&mut out);
+        assert_eq!(out[..], t.output[..]);
     }
 }
 
 pub fn run_variable_tests<D>(tests: &[Test])
-    where D: Input + VariableOutput + Clone + Debug
+    where D: Input + VariableOutput + Reset + Clone + Debug
 {
     let mut buf = [0u8; 1024];
     // Test that it works when accepting the message all at once
@@ -58,7 +137,7 @@ pub fn run_variable_tests<D>(tests: &[Test])
         let mut sh = D::new(t.output.len()).unwrap();
         sh.process(t.input);
 
-        let out = sh.variable_result(&mut buf[..t.output.len()]).unwrap();
+        let out = sh.finalize_variable(&mut buf[..t.output.len()]).unwrap();
 
         assert_eq!(out[..], t.output[..]);
     }
@@ -74,15 +153,39 @@ pub fn run_variable_tests<D>(tests: &[Test])
             left = left - take;
         }
 
-        let out = sh.variable_result(&mut buf[..t.output.len()]).unwrap();
+        // Test that hasher is not reset if buffer has invalid length
+        let n = t.output.len();
+        assert!(sh.finalize_variable_reset(&mut buf[..n + 1]).is_err());
+
+        {
+            let out = sh.finalize_variable_reset(&mut buf[..n]).unwrap();
+            assert_eq!(out[..], t.output[..]);
+        }
 
+        // Test that hasher was reset
+        sh.process(t.input);
+        let out = sh.finalize_variable(&mut buf[..n]).unwrap();
         assert_eq!(out[..], t.output[..]);
     }
+
+    #[cfg(feature = "std")]
+    for t in tests.iter() {
+        let n = t.output.len();
+        let sh = D::new(n).unwrap();
+        let mut r = t.input;
+        let options = ReadOptions::new();
+        assert!(finalize_variable_reader(sh.clone(), &mut r, options,
+            &mut buf[..n + 1]).is_err());
+        let options = ReadOptions::new().buffer_size(7);
+        finalize_variable_reader(sh, &mut r, options, &mut buf[..n])
+            .unwrap();
+        assert_eq!(buf[..n], t.output[..]);
+    }
 }
 
 
 pub fn run_xof_tests<D>(tests: &[Test])
-    where D: Input + ExtendableOutput + Default + Debug + Clone
+    where D: Input + ExtendableOutput + Reset + Default + Debug + Clone
 {
     let mut buf = [0u8; 1024];
     // Test that it works when accepting the message all at once
@@ -91,7 +194,7 @@ pub fn run_xof_tests<D>(tests: &[Test])
         sh.process(t.input);
 
         let out = &mut buf[..t.output.len()];
-        sh.xof_result().read(out);
+        sh.finalize_xof().read(out);
 
         assert_eq!(out[..], t.output[..]);
     }
@@ -108,8 +211,13 @@ pub fn run_xof_tests<D>(tests: &[Test])
         }
 
         let out = &mut buf[..t.output.len()];
-        sh.xof_result().read(out);
+        sh.finalize_xof_reset().read(out);
+
+        assert_eq!(out[..], t.output[..]);
 
+        // Test that hasher was reset
+        sh.process(t.input);
+        sh.finalize_xof().read(out);
         assert_eq!(out[..], t.output[..]);
     }
 
@@ -118,7 +226,7 @@ pub fn run_xof_tests<D>(tests: &[Test])
         let mut sh = D::default();
         sh.process(t.input);
 
-        let mut reader = sh.xof_result();
+        let mut reader = sh.finalize_xof();
         let out = &mut buf[..t.output.len()];
         for chunk in out.chunks_mut(1) {
             reader.read(chunk);
@@ -126,6 +234,294 @@ pub fn run_xof_tests<D>(tests: &[Test])
 
         assert_eq!(out[..], t.output[..]);
     }
+
+    #[cfg(feature = "std")]
+    for t in tests.iter() {
+        let mut r = t.input;
+        let options = ReadOptions::new().buffer_size(7);
+        let mut reader = finalize_xof_reader(D::default(), &mut r, options)
+            .unwrap();
+        let out = &mut buf[..t.output.len()];
+        reader.read(out);
+        assert_eq!(out[..], t.output[..]);
+    }
+}
+
+/// Test that hasher state survives serialization round trip at every split
+/// point of the test inputs and that malformed states are rejected.
+pub fn run_state_tests<D>(tests: &[Test])
+    where D: Digest + SerializableState + Debug + Clone
+{
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        for i in 0..t.input.len() + 1 {
+            let (a, b) = t.input.split_at(i);
+            let mut sh = D::default();
+            sh.input(a);
+
+            let n = sh.serialized_state_len();
+            assert!(sh.serialize_state(&mut buf[..n - 1]).is_err());
+            assert_eq!(sh.serialize_state(&mut buf).unwrap(), n);
+            let state = &buf[..n];
+
+            // truncated states must be rejected
+            for j in 0..n {
+                assert!(D::deserialize_state(&state[..j]).is_err());
+            }
+
+            let mut sh = D::deserialize_state(state).unwrap();
+            sh.input(b);
+            let out = sh.finalize();
+            assert_eq!(out[..], t.output[..]);
+        }
+    }
+
+    // states with trailing data or unknown version must be rejected
+    let mut sh = D::default();
+    sh.input(b"abc");
+    let n = sh.serialize_state(&mut buf).unwrap();
+    assert!(D::deserialize_state(&buf[..n + 1]).is_err());
+    buf[0] = buf[0].wrapping_add(1);
+    assert!(D::deserialize_state(&buf[..n]).is_err());
+}
+
+#[cfg(feature = "std")]
+mod file_utils {
+    use core::fmt::Write as FmtWrite;
+    use std::{fs, io};
+    use std::io::{Seek, SeekFrom, Write};
+    use std::path::PathBuf;
+    use std::string::String;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use super::super::{Digest, ReadOptions, process_file};
+    use super::Test;
+
+    /// Temporary file which is removed on drop
+    struct TempFile {
+        path: PathBuf,
+        file: fs::File,
+    }
+
+    impl TempFile {
+        /// Create new temporary file with the given content
+        fn new(data: &[u8]) -> TempFile {
+            let local = 0u8;
+            loop {
+                let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+                    .map(|d| d.subsec_nanos())
+                    .unwrap_or(0);
+                let mut name = String::new();
+                write!(name, "digest-dev-{:x}-{:x}",
+                    &local as *const u8 as usize, nanos).unwrap();
+                let path = ::std::env::temp_dir().join(name);
+                let res = fs::OpenOptions::new()
+                    .read(true).write(true).create_new(true).open(&path);
+                let mut file = match res {
+                    Ok(file) => file,
+                    Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists =>
+                        continue,
+                    Err(e) => panic!("failed to create temp file: {}", e),
+                };
+                file.write_all(data).unwrap();
+                file.seek(SeekFrom::Start(0)).unwrap();
+                return TempFile { path, file };
+            }
+        }
+
+        /// Append `data` to the file using a separate handle
+        fn append(&self, data: &[u8]) {
+            fs::OpenOptions::new().append(true).open(&self.path).unwrap()
+                .write_all(data).unwrap();
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    /// Check hashing of a file with `process_file` and `Digest::digest_file`
+    pub fn run_file_test<D: Digest>(t: &Test) {
+        let opts = || ReadOptions::new().buffer_size(7);
+        let mut f = TempFile::new(t.input);
+        let out = D::digest_file(&f.file, opts()).unwrap();
+        assert_eq!(out[..], t.output[..]);
+        let len = t.input.len() as u64;
+        assert_eq!(f.file.seek(SeekFrom::Current(0)).unwrap(), len);
+
+        // file at its end and empty file produce hash of empty input
+        let empty = D::digest(&[]);
+        assert_eq!(D::digest_file(&f.file, opts()).unwrap(), empty);
+        let e = TempFile::new(&[]);
+        assert_eq!(D::digest_file(&e.file, opts()).unwrap(), empty);
+
+        // hashing starts at the current position of the file
+        let prefix = b"garbage";
+        let mut data = prefix.to_vec();
+        data.extend_from_slice(t.input);
+        let mut f = TempFile::new(&data);
+        f.file.seek(SeekFrom::Start(prefix.len() as u64)).unwrap();
+        let mut sh = D::default();
+        assert_eq!(process_file(&mut sh, &f.file, opts()).unwrap(), len);
+        assert_eq!(sh.finalize()[..], t.output[..]);
+
+        // data appended after the file was mapped is read as well
+        if t.input.is_empty() {
+            return;
+        }
+        let (head, tail) = t.input.split_at((t.input.len() + 1) / 2);
+        let f = TempFile::new(head);
+        let mut total = 0;
+        let mut sh = D::default();
+        {
+            let mut appended = false;
+            let mut report = |n| {
+                if !appended {
+                    f.append(tail);
+                    appended = true;
+                }
+                total = n;
+            };
+            let options = opts().progress(&mut report);
+            assert_eq!(process_file(&mut sh, &f.file, options).unwrap(), len);
+        }
+        assert_eq!(total, len);
+        assert_eq!(sh.finalize()[..], t.output[..]);
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_utils::{TestAsyncReader, block_on, with_context};
+
+#[cfg(feature = "async")]
+#[clippy::msrv = "1.36"]
+mod async_utils {
+    use super::Test;
+    use super::super::{Digest, InputWriter, process_async_reader};
+    use core::{cmp, mem, ptr};
+    use core::future::Future;
+    use core::pin::Pin;
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    use futures_io::{AsyncRead, AsyncWrite};
+    use std::io;
+    use std::sync::Arc;
+    use std::thread::{self, Thread};
+
+    pub fn run_async_test<D: Digest>(t: &Test) {
+        let mut r = TestAsyncReader::new(t.input);
+        let out = block_on(D::digest_async_reader(&mut r)).unwrap();
+        assert_eq!(out[..], t.output[..]);
+
+        let mut sh = D::default();
+        let mut r = TestAsyncReader::new(t.input);
+        block_on(process_async_reader(&mut sh, &mut r)).unwrap();
+        assert_eq!(sh.finalize()[..], t.output[..]);
+
+        let mut sh = D::default();
+        with_context(|cx| {
+            let mut w = InputWriter::new(&mut sh);
+            for chunk in t.input.chunks(7) {
+                let n = match Pin::new(&mut w).poll_write(cx, chunk) {
+                    Poll::Ready(res) => res.unwrap(),
+                    Poll::Pending => panic!("pending write"),
+                };
+                assert_eq!(n, chunk.len());
+            }
+            assert!(Pin::new(&mut w).poll_close(cx).is_ready());
+        });
+        assert_eq!(sh.finalize()[..], t.output[..]);
+    }
+
+    /// Call `f` with a context which wakers do nothing
+    pub fn with_context<T, F: FnOnce(&mut Context) -> T>(f: F) -> T {
+        unsafe fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(ptr::null(), &VTABLE)
+        }
+        unsafe fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable =
+            RawWakerVTable::new(clone, noop, noop, noop);
+
+        let waker = unsafe { Waker::from_raw(clone(ptr::null())) };
+        f(&mut Context::from_waker(&waker))
+    }
+
+    /// Create waker which unparks `thread`
+    fn thread_waker(thread: Thread) -> Waker {
+        unsafe fn clone(data: *const ()) -> RawWaker {
+            let thread = Arc::from_raw(data as *const Thread);
+            let data = Arc::into_raw(thread.clone()) as *const ();
+            mem::forget(thread);
+            RawWaker::new(data, &VTABLE)
+        }
+        unsafe fn wake(data: *const ()) {
+            Arc::from_raw(data as *const Thread).unpark();
+        }
+        unsafe fn wake_by_ref(data: *const ()) {
+            (*(data as *const Thread)).unpark();
+        }
+        unsafe fn drop_waker(data: *const ()) {
+            Arc::from_raw(data as *const Thread);
+        }
+        static VTABLE: RawWakerVTable =
+            RawWakerVTable::new(clone, wake, wake_by_ref, drop_waker);
+
+        let data = Arc::into_raw(Arc::new(thread)) as *const ();
+        unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }
+    }
+
+    /// Poll `future` until it's ready, parking the current thread until
+    /// the future wakes it if it's pending
+    pub fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+        let waker = thread_waker(thread::current());
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(res) = Pin::new(&mut future).poll(&mut cx) {
+                return res;
+            }
+            thread::park();
+        }
+    }
+
+    /// In-memory reader which returns data in small chunks and alternates
+    /// them with pending and interrupted reads
+    ///
+    /// Pending reads wake the task immediately.
+    pub struct TestAsyncReader<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl<'a> TestAsyncReader<'a> {
+        /// Create new reader returning `data`
+        pub fn new(data: &'a [u8]) -> Self {
+            TestAsyncReader { data, step: 0 }
+        }
+    }
+
+    impl<'a> AsyncRead for TestAsyncReader<'a> {
+        fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context, buf: &mut [u8])
+            -> Poll<io::Result<usize>>
+        {
+            self.step += 1;
+            match self.step % 3 {
+                0 => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                },
+                1 => {
+                    let kind = io::ErrorKind::Interrupted;
+                    Poll::Ready(Err(io::Error::new(kind, "interrupted")))
+                },
+                _ => {
+                    let n = cmp::min(cmp::min(buf.len(), 7), self.data.len());
+                    buf[..n].copy_from_slice(&self.data[..n]);
+                    self.data = &self.data[n..];
+                    Poll::Ready(Ok(n))
+                },
+            }
+        }
+    }
 }
 
 pub fn run_1mil_a_test<D: Digest + Default + Debug + Clone>(expected: &[u8]) {
@@ -134,7 +530,7 @@ pub fn run_1mil_a_test<D: Digest + Default + Debug + Clone>(expected: &[u8]) {
         sh.input(&[b'a'; 10]);
     }
     sh.input(&[b'a'; 500_000]);
-    let out = sh.result();
+    let out = sh.finalize();
     assert_eq!(out[..], expected[..]);
 }
 
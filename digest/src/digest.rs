@@ -1,7 +1,13 @@
-use super::{Input, FixedOutput};
+use super::{Input, FixedOutput, Reset};
 use generic_array::GenericArray;
 #[cfg(feature = "std")]
-use std::io;
+use std::{fs, io};
+#[cfg(feature = "std")]
+use read::{ReadOptions, process_file, process_reader};
+#[cfg(feature = "async")]
+use async_io::DigestAsyncReader;
+#[cfg(feature = "async")]
+use futures_io::AsyncRead;
 
 type Output<N> = GenericArray<u8, N>;
 
@@ -21,9 +27,24 @@ pub trait Digest: Input + FixedOutput + Default {
         self.process(input);
     }
 
+    /// Retrieve result and consume hasher instance
+    fn finalize(self) -> Output<Self::OutputSize> {
+        self.finalize_fixed()
+    }
+
+    /// Retrieve result and reset hasher instance
+    fn finalize_reset(&mut self) -> Output<Self::OutputSize>
+        where Self: Reset + Clone
+    {
+        self.finalize_fixed_reset()
+    }
+
     /// Retrieve result and reset hasher instance
-    fn result(&mut self) -> Output<Self::OutputSize> {
-        self.fixed_result()
+    #[deprecated(note = "use `finalize_reset` instead")]
+    fn result(&mut self) -> Output<Self::OutputSize>
+        where Self: Reset + Clone
+    {
+        self.finalize_reset()
     }
 
     /// Convenience function to compute hash of the `data`. It will handle
@@ -38,7 +59,7 @@ pub trait Digest: Input + FixedOutput + Default {
     fn digest(data: &[u8]) -> Output<Self::OutputSize> {
         let mut hasher = Self::default();
         hasher.process(data);
-        hasher.fixed_result()
+        hasher.finalize_fixed()
     }
 
     /// Convenience function to compute hash of the string. It's equivalent to
@@ -49,8 +70,8 @@ pub trait Digest: Input + FixedOutput + Default {
     }
 
     /// Convenience function which takes `std::io::Read` as a source and computes
-    /// value of digest function `D`, e.g. SHA-2, SHA-3, BLAKE2, etc. using 1 KB
-    /// blocks.
+    /// value of digest function `D`, e.g. SHA-2, SHA-3, BLAKE2, etc. using
+    /// default `ReadOptions`.
     ///
     /// Usage example:
     ///
@@ -64,21 +85,51 @@ pub trait Digest: Input + FixedOutput + Default {
     /// ```
     #[cfg(feature = "std")]
     #[inline]
-    fn digest_reader(source: &mut io::Read)
+    fn digest_reader<R: io::Read + ?Sized>(source: &mut R)
         -> io::Result<Output<Self::OutputSize>>
+    {
+        Self::digest_reader_with(source, ReadOptions::new())
+    }
+
+    /// Compute value of digest function for data from `source` using
+    /// the provided options (buffer size and progress callback).
+    #[cfg(feature = "std")]
+    fn digest_reader_with<R: io::Read + ?Sized>(source: &mut R,
+        options: ReadOptions) -> io::Result<Output<Self::OutputSize>>
     {
         let mut hasher = Self::default();
+        process_reader(&mut hasher, source, options)?;
+        Ok(hasher.finalize())
+    }
 
-        let mut buffer = [0u8; 1024];
-        loop {
-            let bytes_read = source.read(&mut buffer)?;
-            hasher.input(&buffer[..bytes_read]);
-            if bytes_read == 0 {
-                break;
-            }
-        }
+    /// Compute value of digest function for data from the current position
+    /// of `file` up to its end, see `process_file` for details.
+    #[cfg(feature = "std")]
+    fn digest_file(file: &fs::File, options: ReadOptions)
+        -> io::Result<Output<Self::OutputSize>>
+    {
+        let mut hasher = Self::default();
+        process_file(&mut hasher, file, options)?;
+        Ok(hasher.finalize())
+    }
 
-        Ok(hasher.result())
+    /// Compute value of digest function for data from `source` without
+    /// blocking, see `process_async_reader` for details.
+    ///
+    /// Usage example:
+    ///
+    /// ```rust,ignore
+    /// use sha2::{Sha256, Digest};
+    ///
+    /// let result = Sha256::digest_async_reader(&mut stream).await?;
+    /// println!("{:x}", result);
+    /// ```
+    #[cfg(feature = "async")]
+    fn digest_async_reader<'a, R>(source: &'a mut R)
+        -> DigestAsyncReader<'a, Self, R>
+        where R: AsyncRead + Unpin + ?Sized
+    {
+        DigestAsyncReader::new(source)
     }
 }
 
@@ -10,6 +10,10 @@ pub struct InvalidOutputSize;
 #[derive(Clone, Copy, Debug, Default)]
 pub struct InvalidBufferLength;
 
+/// The error type for restoring hasher from serialized state
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InvalidState;
+
 impl fmt::Display for InvalidOutputSize {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str("invalid output size")
@@ -22,6 +26,12 @@ impl fmt::Display for InvalidBufferLength {
     }
 }
 
+impl fmt::Display for InvalidState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid hasher state")
+    }
+}
+
 #[cfg(feature = "std")]
 impl error::Error for InvalidOutputSize {
     fn description(&self) -> &str {
@@ -35,3 +45,10 @@ impl error::Error for InvalidBufferLength {
         "invalid buffer size"
     }
 }
+
+#[cfg(feature = "std")]
+impl error::Error for InvalidState {
+    fn description(&self) -> &str {
+        "invalid hasher state"
+    }
+}
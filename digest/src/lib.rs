@@ -2,22 +2,55 @@
 //! functions.
 //!
 //! By default std functionality in this crate disabled. (e.g. method for
-//! hashing `Read`ers) To enable it turn on `std` feature in your `Cargo.toml`
-//! for this crate.
+//! hashing `Read`ers, `io::Write` implementation for `InputWriter` and
+//! `HashingReader`/`HashingWriter` adapters) To enable it turn on `std`
+//! feature in your `Cargo.toml` for this crate. With enabled `mmap` feature
+//! `process_file` and `Digest::digest_file` memory map hashed files.
+//!
+//! With enabled `async` feature this crate provides `process_async_reader`
+//! and `Digest::digest_async_reader` for hashing `futures_io::AsyncRead`
+//! without blocking, and `InputWriter` implements `futures_io::AsyncWrite`.
+//! This feature requires Rust 1.36 or later.
 #![no_std]
 pub extern crate generic_array;
 
 #[cfg(feature = "std")]
 extern crate std;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "async")]
+pub extern crate futures_io;
 use generic_array::{GenericArray, ArrayLength};
 
 mod digest;
 mod errors;
+mod state;
+mod writer;
+#[cfg(feature = "std")]
+mod tee;
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "async")]
+#[clippy::msrv = "1.36"]
+mod async_io;
 #[cfg(feature = "dev")]
 pub mod dev;
 
-pub use errors::{InvalidOutputSize, InvalidBufferLength};
+pub use errors::{InvalidOutputSize, InvalidBufferLength, InvalidState};
 pub use digest::Digest;
+pub use state::{SerializableState, MdState, MD_STATE_VERSION};
+pub use writer::InputWriter;
+#[cfg(feature = "std")]
+pub use tee::{HashingReader, HashingWriter};
+#[cfg(feature = "std")]
+pub use read::{
+    ReadOptions, DEFAULT_BUFFER_SIZE, process_reader, process_file,
+    finalize_variable_reader, finalize_xof_reader,
+};
+#[cfg(feature = "async")]
+pub use async_io::{
+    ProcessAsyncReader, DigestAsyncReader, process_async_reader,
+};
 
 // `process` is choosen to not overlap with `input` method in the digest trait
 // change it on trait alias stabilization
@@ -29,18 +62,71 @@ pub trait Input {
     fn process(&mut self, input: &[u8]);
 }
 
+impl<'a, D: Input + ?Sized + 'a> Input for &'a mut D {
+    #[inline]
+    fn process(&mut self, input: &[u8]) {
+        (**self).process(input);
+    }
+}
+
 /// Trait to indicate that digest function processes data in blocks of size
 /// `BlockSize`. Main usage of this trait is for implementing HMAC generically.
 pub trait BlockInput {
     type BlockSize: ArrayLength<u8>;
 }
 
+/// Trait for resetting hasher instances to their initial state
+pub trait Reset {
+    /// Reset hasher instance to its initial state.
+    fn reset(&mut self);
+}
+
 /// Trait for returning digest result with the fixed size
-pub trait FixedOutput {
+pub trait FixedOutput: core::marker::Sized {
     type OutputSize: ArrayLength<u8>;
 
+    /// Retrieve result and consume hasher instance.
+    fn finalize_fixed(self) -> GenericArray<u8, Self::OutputSize>;
+
     /// Retrieve result and reset hasher instance.
-    fn fixed_result(&mut self) -> GenericArray<u8, Self::OutputSize>;
+    ///
+    /// Default implementation finalizes a copy of the hasher state,
+    /// implementors are encouraged to override it with a more efficient
+    /// variant.
+    fn finalize_fixed_reset(&mut self) -> GenericArray<u8, Self::OutputSize>
+        where Self: Reset + Clone
+    {
+        let res = self.clone().finalize_fixed();
+        self.reset();
+        res
+    }
+
+    /// Write result into `out` and consume hasher instance.
+    ///
+    /// Default implementation delegates to `finalize_fixed`, implementors
+    /// can override it to write result directly into `out`.
+    fn finalize_into(self, out: &mut GenericArray<u8, Self::OutputSize>) {
+        *out = self.finalize_fixed();
+    }
+
+    /// Retrieve result and reset hasher instance.
+    #[deprecated(note = "use `finalize_fixed_reset` instead")]
+    fn fixed_result(&mut self) -> GenericArray<u8, Self::OutputSize>
+        where Self: Reset + Clone
+    {
+        self.finalize_fixed_reset()
+    }
+
+    /// Write result into `out` and reset hasher instance.
+    ///
+    /// Default implementation delegates to `finalize_fixed_reset`.
+    fn finalize_into_reset(
+        &mut self, out: &mut GenericArray<u8, Self::OutputSize>,
+    )
+        where Self: Reset + Clone
+    {
+        *out = self.finalize_fixed_reset();
+    }
 }
 
 /// Trait for returning digest result with the varaible size
@@ -53,13 +139,38 @@ pub trait VariableOutput: core::marker::Sized {
     /// Get output size of the hasher instance provided to the `new` method
     fn output_size(&self) -> usize;
 
+    /// Retrieve result into provided buffer and consume hasher instance.
+    ///
+    /// Length of the buffer must be equal to output size provided to the `new`
+    /// method, otherwise `Err(InvalidBufferLength)` will be returned.
+    fn finalize_variable(self, buffer: &mut [u8])
+        -> Result<&[u8], InvalidBufferLength>;
+
     /// Retrieve result into provided buffer and reset hasher instance.
     ///
     /// Length of the buffer must be equal to output size provided to the `new`
     /// method, otherwise `Err(InvalidBufferLength)` will be returned without
     /// resetting hasher.
-    fn variable_result(&mut self, buffer: &mut [u8])
-        -> Result<&[u8], InvalidBufferLength>;
+    fn finalize_variable_reset<'a>(&mut self, buffer: &'a mut [u8])
+        -> Result<&'a [u8], InvalidBufferLength>
+        where Self: Reset + Clone
+    {
+        if buffer.len() != self.output_size() {
+            return Err(InvalidBufferLength);
+        }
+        let res = self.clone().finalize_variable(buffer);
+        self.reset();
+        res
+    }
+
+    /// Retrieve result into provided buffer and reset hasher instance.
+    #[deprecated(note = "use `finalize_variable_reset` instead")]
+    fn variable_result<'a>(&mut self, buffer: &'a mut [u8])
+        -> Result<&'a [u8], InvalidBufferLength>
+        where Self: Reset + Clone
+    {
+        self.finalize_variable_reset(buffer)
+    }
 }
 
 /// Trait for decribing readers which are used to extract extendable output
@@ -72,9 +183,26 @@ pub trait XofReader {
 /// Trait which describes extendable output (XOF) of hash functions. Using this
 /// trait you first need to get structure which implements `XofReader`, using
 /// which you can read extendable output.
-pub trait ExtendableOutput {
+pub trait ExtendableOutput: core::marker::Sized {
     type Reader: XofReader;
 
+    /// Retrieve XOF reader and consume hasher instance.
+    fn finalize_xof(self) -> Self::Reader;
+
+    /// Retrieve XOF reader and reset hasher instance.
+    fn finalize_xof_reset(&mut self) -> Self::Reader
+        where Self: Reset + Clone
+    {
+        let reader = self.clone().finalize_xof();
+        self.reset();
+        reader
+    }
+
     /// Retrieve XOF reader and reset hasher instance.
-    fn xof_result(&mut self) -> Self::Reader;
+    #[deprecated(note = "use `finalize_xof_reset` instead")]
+    fn xof_result(&mut self) -> Self::Reader
+        where Self: Reset + Clone
+    {
+        self.finalize_xof_reset()
+    }
 }
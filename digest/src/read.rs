@@ -0,0 +1,204 @@
+use super::{Input, VariableOutput, ExtendableOutput};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+#[cfg(feature = "mmap")]
+use std::io::{Seek, SeekFrom};
+use std::iter;
+use std::vec::Vec;
+#[cfg(feature = "mmap")]
+use memmap2::MmapOptions;
+
+/// Default size of buffer used for reading data
+pub const DEFAULT_BUFFER_SIZE: usize = 8*1024;
+
+/// Options for feeding hashers with data from readers.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use digest::{Digest, ReadOptions};
+///
+/// let mut report = |n: u64| println!("hashed {} bytes", n);
+/// let options = ReadOptions::new()
+///     .buffer_size(64*1024)
+///     .progress(&mut report);
+/// let result = sha2::Sha256::digest_file(&file, options)?;
+/// ```
+pub struct ReadOptions<'a> {
+    buffer_size: usize,
+    progress: Option<&'a mut FnMut(u64)>,
+}
+
+impl<'a> ReadOptions<'a> {
+    /// Create options with `DEFAULT_BUFFER_SIZE` buffer and without
+    /// progress callback
+    pub fn new() -> Self {
+        ReadOptions { buffer_size: DEFAULT_BUFFER_SIZE, progress: None }
+    }
+
+    /// Set size of buffer used for reading data, it's also used as size of
+    /// chunks between progress callback calls for memory mapped files.
+    ///
+    /// # Panics
+    /// If `size` is equal to zero.
+    pub fn buffer_size(mut self, size: usize) -> Self {
+        assert!(size != 0, "buffer size must not be zero");
+        self.buffer_size = size;
+        self
+    }
+
+    /// Set callback which is called after processing of every chunk of
+    /// data with total number of processed bytes.
+    pub fn progress(mut self, f: &'a mut FnMut(u64)) -> Self {
+        self.progress = Some(f);
+        self
+    }
+
+    fn report(&mut self, total: u64) {
+        if let Some(ref mut f) = self.progress {
+            f(total);
+        }
+    }
+}
+
+impl<'a> Default for ReadOptions<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> fmt::Debug for ReadOptions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ReadOptions")
+            .field("buffer_size", &self.buffer_size)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+/// Feed all data from `reader` into `hasher` and return number of
+/// processed bytes.
+///
+/// Reads interrupted by signals (i.e. failed with `ErrorKind::Interrupted`)
+/// are retried, other errors are returned immediately.
+pub fn process_reader<D, R>(hasher: &mut D, reader: &mut R,
+    mut options: ReadOptions) -> io::Result<u64>
+    where D: Input + ?Sized, R: Read + ?Sized
+{
+    let mut buffer: Vec<u8> = iter::repeat(0)
+        .take(options.buffer_size)
+        .collect();
+    let mut total = 0u64;
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(total),
+            Ok(n) => n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.process(&buffer[..n]);
+        total += n as u64;
+        options.report(total);
+    }
+}
+
+/// Feed data from the current position of `file` up to its end into
+/// `hasher` and return number of processed bytes.
+///
+/// With enabled `mmap` feature file is memory mapped instead of being read
+/// into buffer, which is usually faster for big files. If file can not be
+/// mapped (e.g. if it's a pipe), this function falls back to reading.
+///
+/// Note that modification of a memory mapped file by another process while
+/// it's being hashed can result in an inconsistent hash value or in
+/// termination of the process (e.g. if file gets truncated), so the `mmap`
+/// feature should be enabled only if files are not expected to change.
+pub fn process_file<D>(hasher: &mut D, file: &fs::File,
+    options: ReadOptions) -> io::Result<u64>
+    where D: Input + ?Sized
+{
+    #[cfg(feature = "mmap")]
+    {
+        if let Some(mmap) = map_file(file)? {
+            return process_mapped(hasher, file, &mmap, options);
+        }
+    }
+    let mut file = file;
+    process_reader(hasher, &mut file, options)
+}
+
+/// Feed memory mapped part of `file` into `hasher` followed by data which
+/// may have been appended after the file was mapped.
+#[cfg(feature = "mmap")]
+fn process_mapped<D>(hasher: &mut D, mut file: &fs::File, mmap: &[u8],
+    mut options: ReadOptions) -> io::Result<u64>
+    where D: Input + ?Sized
+{
+    let mut total = 0u64;
+    for chunk in mmap.chunks(options.buffer_size) {
+        hasher.process(chunk);
+        total += chunk.len() as u64;
+        options.report(total);
+    }
+    file.seek(SeekFrom::Current(total as i64))?;
+
+    let mut progress = options.progress.take();
+    let mut report = |n: u64| {
+        if let Some(ref mut f) = progress {
+            f(total + n);
+        }
+    };
+    let options = ReadOptions {
+        buffer_size: options.buffer_size,
+        progress: Some(&mut report),
+    };
+    let n = process_reader(hasher, &mut file, options)?;
+    Ok(total + n)
+}
+
+/// Map part of `file` from its current position to the end, returns `None`
+/// if there is nothing to map or if file can not be mapped.
+#[cfg(feature = "mmap")]
+fn map_file(mut file: &fs::File) -> io::Result<Option<memmap2::Mmap>> {
+    let meta = file.metadata()?;
+    if !meta.is_file() {
+        return Ok(None);
+    }
+    let pos = file.seek(SeekFrom::Current(0))?;
+    if pos >= meta.len() || meta.len() - pos > usize::max_value() as u64 {
+        return Ok(None);
+    }
+    let len = (meta.len() - pos) as usize;
+    // Memory map is valid as long as the file is not modified, which is
+    // documented as a requirement in `process_file`
+    let res = unsafe { MmapOptions::new().offset(pos).len(len).map(file) };
+    Ok(res.ok())
+}
+
+/// Feed all data from `reader` into `hasher` and write result into `out`.
+///
+/// Length of `out` must be equal to the output size of `hasher`,
+/// otherwise error of `ErrorKind::InvalidInput` kind will be returned.
+pub fn finalize_variable_reader<D, R>(mut hasher: D, reader: &mut R,
+    options: ReadOptions, out: &mut [u8]) -> io::Result<()>
+    where D: Input + VariableOutput, R: Read + ?Sized
+{
+    if out.len() != hasher.output_size() {
+        let msg = "invalid output buffer length";
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    process_reader(&mut hasher, reader, options)?;
+    hasher.finalize_variable(out)
+        .expect("buffer has valid length");
+    Ok(())
+}
+
+/// Feed all data from `reader` into `hasher` and return XOF reader.
+pub fn finalize_xof_reader<D, R>(mut hasher: D, reader: &mut R,
+    options: ReadOptions) -> io::Result<D::Reader>
+    where D: Input + ExtendableOutput, R: Read + ?Sized
+{
+    process_reader(&mut hasher, reader, options)?;
+    Ok(hasher.finalize_xof())
+}
@@ -0,0 +1,184 @@
+use errors::{InvalidBufferLength, InvalidState};
+#[cfg(feature = "std")]
+use core::iter;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+/// Version of the encoding produced by `MdState::encode`
+pub const MD_STATE_VERSION: u8 = 1;
+
+/// Trait for hashers which internal state can be exported into a byte
+/// encoding and restored from it, e.g. for checkpointing hashing of large
+/// messages across processes.
+///
+/// The first byte of the encoding is its version, which allows to change
+/// encoding of a hasher state while rejecting states in unknown formats.
+/// Merkle–Damgård hashers can use `MdState` for implementing this trait.
+///
+/// Serialized state contains unprocessed message bytes, so it should be
+/// protected in the same way as the message itself.
+pub trait SerializableState: Sized {
+    /// Length of the serialized state in bytes
+    fn serialized_state_len(&self) -> usize;
+
+    /// Serialize hasher state into `out` and return number of written
+    /// bytes.
+    ///
+    /// Returns `Err(InvalidBufferLength)` if `out` is shorter than
+    /// `serialized_state_len`.
+    fn serialize_state(&self, out: &mut [u8])
+        -> Result<usize, InvalidBufferLength>;
+
+    /// Restore hasher from serialized state.
+    ///
+    /// Returns `Err(InvalidState)` if `data` is not a valid state of this
+    /// hasher or was produced by an unsupported encoding version.
+    fn deserialize_state(data: &[u8]) -> Result<Self, InvalidState>;
+
+    /// Serialize hasher state into a newly allocated vector.
+    #[cfg(feature = "std")]
+    fn serialize_state_vec(&self) -> Vec<u8> {
+        let n = self.serialized_state_len();
+        let mut buf: Vec<u8> = iter::repeat(0).take(n).collect();
+        let n = self.serialize_state(&mut buf)
+            .expect("buffer has valid length");
+        buf.truncate(n);
+        buf
+    }
+}
+
+/// State of a Merkle–Damgård hasher, used as a helper for implementing
+/// `SerializableState`.
+///
+/// State is encoded as follows (integers are big endian):
+///
+/// | Field                                    | Size            |
+/// |------------------------------------------|-----------------|
+/// | Encoding version (`MD_STATE_VERSION`)    | 1               |
+/// | Algorithm identifier length              | 1               |
+/// | Algorithm identifier                     | variable        |
+/// | Chaining value length                    | 1               |
+/// | Chaining value                           | variable        |
+/// | Number of processed bytes                | 8               |
+/// | Number of buffered bytes                 | 2               |
+/// | Buffered bytes                           | variable        |
+///
+/// Encoding of the chaining value (e.g. endianness of its words) is
+/// defined by the hasher. Number of processed bytes includes buffered
+/// bytes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MdState<'a> {
+    /// Algorithm identifier, e.g. `b"sha256"`, used to prevent restoring
+    /// state of one hasher into another one with the same state layout
+    pub algorithm: &'a [u8],
+    /// Current chaining value
+    pub chaining_value: &'a [u8],
+    /// Bytes of the incomplete block
+    pub buffer: &'a [u8],
+    /// Number of processed bytes
+    pub length: u64,
+}
+
+impl<'a> MdState<'a> {
+    /// Length of the encoded state in bytes.
+    pub fn encoded_len(&self) -> usize {
+        1 + 1 + self.algorithm.len() + 1 + self.chaining_value.len()
+            + 8 + 2 + self.buffer.len()
+    }
+
+    /// Encode state into `out` and return number of written bytes.
+    ///
+    /// Returns `Err(InvalidBufferLength)` if `out` is shorter than
+    /// `encoded_len`.
+    ///
+    /// # Panics
+    /// If algorithm identifier or chaining value is longer than 255 bytes,
+    /// or if buffer is longer than 65535 bytes.
+    pub fn encode(&self, out: &mut [u8])
+        -> Result<usize, InvalidBufferLength>
+    {
+        assert!(self.algorithm.len() <= 0xFF, "algorithm id is too long");
+        assert!(self.chaining_value.len() <= 0xFF,
+            "chaining value is too long");
+        assert!(self.buffer.len() <= 0xFFFF, "buffer is too long");
+        let n = self.encoded_len();
+        if out.len() < n {
+            return Err(InvalidBufferLength);
+        }
+
+        let mut pos = 0;
+        {
+            let mut put = |data: &[u8]| {
+                out[pos..pos + data.len()].copy_from_slice(data);
+                pos += data.len();
+            };
+            put(&[MD_STATE_VERSION, self.algorithm.len() as u8]);
+            put(self.algorithm);
+            put(&[self.chaining_value.len() as u8]);
+            put(self.chaining_value);
+            let mut length = [0u8; 8];
+            for (i, b) in length.iter_mut().enumerate() {
+                *b = (self.length >> (56 - 8*i)) as u8;
+            }
+            put(&length);
+            let buf_len = self.buffer.len();
+            put(&[(buf_len >> 8) as u8, buf_len as u8]);
+            put(self.buffer);
+        }
+        debug_assert_eq!(pos, n);
+        Ok(n)
+    }
+
+    /// Decode state from `data` produced by hasher with identifier
+    /// `algorithm`, chaining value length `chaining_len` and block size
+    /// `block_size` in bytes.
+    ///
+    /// Returns `Err(InvalidState)` if `data` is malformed, uses unsupported
+    /// encoding version, was produced by a different algorithm, or if
+    /// decoded values do not agree with the provided parameters.
+    pub fn decode(
+        data: &'a [u8], algorithm: &[u8], chaining_len: usize,
+        block_size: usize,
+    ) -> Result<Self, InvalidState> {
+        let mut data = data;
+        if take(&mut data, 1)?[0] != MD_STATE_VERSION {
+            return Err(InvalidState);
+        }
+        let n = take(&mut data, 1)?[0] as usize;
+        let id = take(&mut data, n)?;
+        if id != algorithm {
+            return Err(InvalidState);
+        }
+        let n = take(&mut data, 1)?[0] as usize;
+        if n != chaining_len {
+            return Err(InvalidState);
+        }
+        let chaining_value = take(&mut data, n)?;
+        let length = take(&mut data, 8)?.iter()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        let n = take(&mut data, 2)?;
+        let n = ((n[0] as usize) << 8) | (n[1] as usize);
+        let buffer = take(&mut data, n)?;
+        let valid = data.is_empty() && n < block_size
+            && length % (block_size as u64) == n as u64;
+        if !valid {
+            return Err(InvalidState);
+        }
+        Ok(MdState {
+            algorithm: id,
+            chaining_value,
+            buffer,
+            length,
+        })
+    }
+}
+
+/// Split off the first `n` bytes of `data`
+fn take<'a>(data: &mut &'a [u8], n: usize) -> Result<&'a [u8], InvalidState> {
+    if data.len() < n {
+        return Err(InvalidState);
+    }
+    let (a, b) = data.split_at(n);
+    *data = b;
+    Ok(a)
+}
@@ -0,0 +1,121 @@
+use super::Input;
+use std::io;
+
+/// Reader adapter which feeds all data read from the wrapped reader into
+/// hasher.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use std::{fs, io};
+/// use digest::{Digest, HashingReader};
+///
+/// let mut reader = HashingReader::new(response, sha2::Sha256::new());
+/// io::copy(&mut reader, &mut fs::File::create("download.bin")?)?;
+/// let (_, hasher) = reader.into_inner();
+/// println!("{:x}", hasher.finalize());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashingReader<R: io::Read, D: Input> {
+    reader: R,
+    hasher: D,
+}
+
+impl<R: io::Read, D: Input> HashingReader<R, D> {
+    /// Create new adapter from reader and hasher
+    pub fn new(reader: R, hasher: D) -> Self {
+        HashingReader { reader, hasher }
+    }
+
+    /// Get reference to the wrapped reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Get mutable reference to the wrapped reader.
+    ///
+    /// Data read directly from the reader is not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Get reference to the hasher
+    pub fn hasher(&self) -> &D {
+        &self.hasher
+    }
+
+    /// Unwrap the adapter, returning the wrapped reader and the hasher
+    pub fn into_inner(self) -> (R, D) {
+        (self.reader, self.hasher)
+    }
+}
+
+impl<R: io::Read, D: Input> io::Read for HashingReader<R, D> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.hasher.process(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Writer adapter which feeds all data successfully written into the
+/// wrapped writer into hasher.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use std::{fs, io};
+/// use digest::{Digest, HashingWriter};
+///
+/// let file = fs::File::create("download.bin")?;
+/// let mut writer = HashingWriter::new(file, sha2::Sha256::new());
+/// io::copy(&mut response, &mut writer)?;
+/// let (_, hasher) = writer.into_inner();
+/// println!("{:x}", hasher.finalize());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashingWriter<W: io::Write, D: Input> {
+    writer: W,
+    hasher: D,
+}
+
+impl<W: io::Write, D: Input> HashingWriter<W, D> {
+    /// Create new adapter from writer and hasher
+    pub fn new(writer: W, hasher: D) -> Self {
+        HashingWriter { writer, hasher }
+    }
+
+    /// Get reference to the wrapped writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get mutable reference to the wrapped writer.
+    ///
+    /// Data written directly into the writer is not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Get reference to the hasher
+    pub fn hasher(&self) -> &D {
+        &self.hasher
+    }
+
+    /// Unwrap the adapter, returning the wrapped writer and the hasher
+    pub fn into_inner(self) -> (W, D) {
+        (self.writer, self.hasher)
+    }
+}
+
+impl<W: io::Write, D: Input> io::Write for HashingWriter<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.writer.write(buf)?;
+        self.hasher.process(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
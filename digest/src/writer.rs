@@ -0,0 +1,71 @@
+use super::Input;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Adapter which implements `fmt::Write` and, with enabled `std` feature,
+/// `io::Write` by forwarding written data to `Input::process`.
+///
+/// It allows to feed hasher using `write!` macro or `io::copy`. To keep
+/// ownership of the hasher wrap a mutable reference to it.
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// use std::io;
+/// use digest::{Digest, InputWriter};
+///
+/// let mut hasher = sha2::Sha256::new();
+/// io::copy(&mut file, &mut InputWriter::new(&mut hasher))?;
+/// let result = hasher.finalize();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InputWriter<D: Input> {
+    hasher: D,
+}
+
+impl<D: Input> InputWriter<D> {
+    /// Create new adapter wrapping `hasher`
+    pub fn new(hasher: D) -> Self {
+        InputWriter { hasher }
+    }
+
+    /// Get reference to the wrapped hasher
+    pub fn get_ref(&self) -> &D {
+        &self.hasher
+    }
+
+    /// Get mutable reference to the wrapped hasher
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.hasher
+    }
+
+    /// Unwrap the adapter, returning the wrapped hasher
+    pub fn into_inner(self) -> D {
+        self.hasher
+    }
+}
+
+impl<D: Input> fmt::Write for InputWriter<D> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.hasher.process(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D: Input> io::Write for InputWriter<D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.process(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.hasher.process(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
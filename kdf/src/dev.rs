@@ -0,0 +1,1579 @@
+//! Test vectors and helpers for testing KDFs with concrete hash functions.
+use digest::{BlockInput, Digest, FixedOutput, Input};
+use digest::generic_array::GenericArray;
+use digest::generic_array::typenum::Unsigned;
+use crypto_mac::Mac;
+use super::{Hkdf, InvalidLength, pbkdf2};
+#[cfg(feature = "parallel")]
+use super::pbkdf2_par;
+use super::{CounterLocation, Kbkdf, KbkdfError, KbkdfMode};
+use super::{concat_kdf, concat_kdf_mac, x963_kdf};
+use super::tls;
+
+/// HKDF test vector
+pub struct HkdfTest {
+    pub salt: &'static [u8],
+    pub ikm: &'static [u8],
+    pub info: &'static [u8],
+    pub prk: &'static [u8],
+    pub okm: &'static [u8],
+}
+
+/// HKDF-SHA256 test vectors from RFC 5869, test cases 1-3
+pub const HKDF_SHA256: &[HkdfTest] = &[
+    HkdfTest {
+        salt: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ],
+        ikm: &[
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+        ],
+        info: &[
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+            0xf8, 0xf9,
+        ],
+        prk: &[
+            0x07, 0x77, 0x09, 0x36, 0x2c, 0x2e, 0x32, 0xdf,
+            0x0d, 0xdc, 0x3f, 0x0d, 0xc4, 0x7b, 0xba, 0x63,
+            0x90, 0xb6, 0xc7, 0x3b, 0xb5, 0x0f, 0x9c, 0x31,
+            0x22, 0xec, 0x84, 0x4a, 0xd7, 0xc2, 0xb3, 0xe5,
+        ],
+        okm: &[
+            0x3c, 0xb2, 0x5f, 0x25, 0xfa, 0xac, 0xd5, 0x7a,
+            0x90, 0x43, 0x4f, 0x64, 0xd0, 0x36, 0x2f, 0x2a,
+            0x2d, 0x2d, 0x0a, 0x90, 0xcf, 0x1a, 0x5a, 0x4c,
+            0x5d, 0xb0, 0x2d, 0x56, 0xec, 0xc4, 0xc5, 0xbf,
+            0x34, 0x00, 0x72, 0x08, 0xd5, 0xb8, 0x87, 0x18,
+            0x58, 0x65,
+        ],
+    },
+    HkdfTest {
+        salt: &[
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
+            0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
+            0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+            0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
+            0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+            0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+            0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+            0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+            0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+            0xa8, 0xa9, 0xaa, 0xab, 0xac, 0xad, 0xae, 0xaf,
+        ],
+        ikm: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+        ],
+        info: &[
+            0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7,
+            0xb8, 0xb9, 0xba, 0xbb, 0xbc, 0xbd, 0xbe, 0xbf,
+            0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+            0xc8, 0xc9, 0xca, 0xcb, 0xcc, 0xcd, 0xce, 0xcf,
+            0xd0, 0xd1, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7,
+            0xd8, 0xd9, 0xda, 0xdb, 0xdc, 0xdd, 0xde, 0xdf,
+            0xe0, 0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7,
+            0xe8, 0xe9, 0xea, 0xeb, 0xec, 0xed, 0xee, 0xef,
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+            0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        ],
+        prk: &[
+            0x06, 0xa6, 0xb8, 0x8c, 0x58, 0x53, 0x36, 0x1a,
+            0x06, 0x10, 0x4c, 0x9c, 0xeb, 0x35, 0xb4, 0x5c,
+            0xef, 0x76, 0x00, 0x14, 0x90, 0x46, 0x71, 0x01,
+            0x4a, 0x19, 0x3f, 0x40, 0xc1, 0x5f, 0xc2, 0x44,
+        ],
+        okm: &[
+            0xb1, 0x1e, 0x39, 0x8d, 0xc8, 0x03, 0x27, 0xa1,
+            0xc8, 0xe7, 0xf7, 0x8c, 0x59, 0x6a, 0x49, 0x34,
+            0x4f, 0x01, 0x2e, 0xda, 0x2d, 0x4e, 0xfa, 0xd8,
+            0xa0, 0x50, 0xcc, 0x4c, 0x19, 0xaf, 0xa9, 0x7c,
+            0x59, 0x04, 0x5a, 0x99, 0xca, 0xc7, 0x82, 0x72,
+            0x71, 0xcb, 0x41, 0xc6, 0x5e, 0x59, 0x0e, 0x09,
+            0xda, 0x32, 0x75, 0x60, 0x0c, 0x2f, 0x09, 0xb8,
+            0x36, 0x77, 0x93, 0xa9, 0xac, 0xa3, 0xdb, 0x71,
+            0xcc, 0x30, 0xc5, 0x81, 0x79, 0xec, 0x3e, 0x87,
+            0xc1, 0x4c, 0x01, 0xd5, 0xc1, 0xf3, 0x43, 0x4f,
+            0x1d, 0x87,
+        ],
+    },
+    HkdfTest {
+        salt: &[],
+        ikm: &[
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+        ],
+        info: &[],
+        prk: &[
+            0x19, 0xef, 0x24, 0xa3, 0x2c, 0x71, 0x7b, 0x16,
+            0x7f, 0x33, 0xa9, 0x1d, 0x6f, 0x64, 0x8b, 0xdf,
+            0x96, 0x59, 0x67, 0x76, 0xaf, 0xdb, 0x63, 0x77,
+            0xac, 0x43, 0x4c, 0x1c, 0x29, 0x3c, 0xcb, 0x04,
+        ],
+        okm: &[
+            0x8d, 0xa4, 0xe7, 0x75, 0xa5, 0x63, 0xc1, 0x8f,
+            0x71, 0x5f, 0x80, 0x2a, 0x06, 0x3c, 0x5a, 0x31,
+            0xb8, 0xa1, 0x1f, 0x5c, 0x5e, 0xe1, 0x87, 0x9e,
+            0xc3, 0x45, 0x4e, 0x5f, 0x3c, 0x73, 0x8d, 0x2d,
+            0x9d, 0x20, 0x13, 0x95, 0xfa, 0xa4, 0xb6, 0x1a,
+            0x96, 0xc8,
+        ],
+    },
+];
+
+/// HKDF-SHA1 test vectors from RFC 5869, test cases 4-7
+pub const HKDF_SHA1: &[HkdfTest] = &[
+    HkdfTest {
+        salt: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ],
+        ikm: &[
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b,
+        ],
+        info: &[
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+            0xf8, 0xf9,
+        ],
+        prk: &[
+            0x9b, 0x6c, 0x18, 0xc4, 0x32, 0xa7, 0xbf, 0x8f,
+            0x0e, 0x71, 0xc8, 0xeb, 0x88, 0xf4, 0xb3, 0x0b,
+            0xaa, 0x2b, 0xa2, 0x43,
+        ],
+        okm: &[
+            0x08, 0x5a, 0x01, 0xea, 0x1b, 0x10, 0xf3, 0x69,
+            0x33, 0x06, 0x8b, 0x56, 0xef, 0xa5, 0xad, 0x81,
+            0xa4, 0xf1, 0x4b, 0x82, 0x2f, 0x5b, 0x09, 0x15,
+            0x68, 0xa9, 0xcd, 0xd4, 0xf1, 0x55, 0xfd, 0xa2,
+            0xc2, 0x2e, 0x42, 0x24, 0x78, 0xd3, 0x05, 0xf3,
+            0xf8, 0x96,
+        ],
+    },
+    HkdfTest {
+        salt: &[
+            0x60, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67,
+            0x68, 0x69, 0x6a, 0x6b, 0x6c, 0x6d, 0x6e, 0x6f,
+            0x70, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76, 0x77,
+            0x78, 0x79, 0x7a, 0x7b, 0x7c, 0x7d, 0x7e, 0x7f,
+            0x80, 0x81, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+            0x88, 0x89, 0x8a, 0x8b, 0x8c, 0x8d, 0x8e, 0x8f,
+            0x90, 0x91, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97,
+            0x98, 0x99, 0x9a, 0x9b, 0x9c, 0x9d, 0x9e, 0x9f,
+            0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+            0xa8, 0xa9, 0xaa, 0xab, 0xac, 0xad, 0xae, 0xaf,
+        ],
+        ikm: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27,
+            0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47,
+            0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f,
+        ],
+        info: &[
+            0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7,
+            0xb8, 0xb9, 0xba, 0xbb, 0xbc, 0xbd, 0xbe, 0xbf,
+            0xc0, 0xc1, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7,
+            0xc8, 0xc9, 0xca, 0xcb, 0xcc, 0xcd, 0xce, 0xcf,
+            0xd0, 0xd1, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7,
+            0xd8, 0xd9, 0xda, 0xdb, 0xdc, 0xdd, 0xde, 0xdf,
+            0xe0, 0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7,
+            0xe8, 0xe9, 0xea, 0xeb, 0xec, 0xed, 0xee, 0xef,
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7,
+            0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+        ],
+        prk: &[
+            0x8a, 0xda, 0xe0, 0x9a, 0x2a, 0x30, 0x70, 0x59,
+            0x47, 0x8d, 0x30, 0x9b, 0x26, 0xc4, 0x11, 0x5a,
+            0x22, 0x4c, 0xfa, 0xf6,
+        ],
+        okm: &[
+            0x0b, 0xd7, 0x70, 0xa7, 0x4d, 0x11, 0x60, 0xf7,
+            0xc9, 0xf1, 0x2c, 0xd5, 0x91, 0x2a, 0x06, 0xeb,
+            0xff, 0x6a, 0xdc, 0xae, 0x89, 0x9d, 0x92, 0x19,
+            0x1f, 0xe4, 0x30, 0x56, 0x73, 0xba, 0x2f, 0xfe,
+            0x8f, 0xa3, 0xf1, 0xa4, 0xe5, 0xad, 0x79, 0xf3,
+            0xf3, 0x34, 0xb3, 0xb2, 0x02, 0xb2, 0x17, 0x3c,
+            0x48, 0x6e, 0xa3, 0x7c, 0xe3, 0xd3, 0x97, 0xed,
+            0x03, 0x4c, 0x7f, 0x9d, 0xfe, 0xb1, 0x5c, 0x5e,
+            0x92, 0x73, 0x36, 0xd0, 0x44, 0x1f, 0x4c, 0x43,
+            0x00, 0xe2, 0xcf, 0xf0, 0xd0, 0x90, 0x0b, 0x52,
+            0xd3, 0xb4,
+        ],
+    },
+    HkdfTest {
+        salt: &[],
+        ikm: &[
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+            0x0b, 0x0b, 0x0b, 0x0b, 0x0b, 0x0b,
+        ],
+        info: &[],
+        prk: &[
+            0xda, 0x8c, 0x8a, 0x73, 0xc7, 0xfa, 0x77, 0x28,
+            0x8e, 0xc6, 0xf5, 0xe7, 0xc2, 0x97, 0x78, 0x6a,
+            0xa0, 0xd3, 0x2d, 0x01,
+        ],
+        okm: &[
+            0x0a, 0xc1, 0xaf, 0x70, 0x02, 0xb3, 0xd7, 0x61,
+            0xd1, 0xe5, 0x52, 0x98, 0xda, 0x9d, 0x05, 0x06,
+            0xb9, 0xae, 0x52, 0x05, 0x72, 0x20, 0xa3, 0x06,
+            0xe0, 0x7b, 0x6b, 0x87, 0xe8, 0xdf, 0x21, 0xd0,
+            0xea, 0x00, 0x03, 0x3d, 0xe0, 0x39, 0x84, 0xd3,
+            0x49, 0x18,
+        ],
+    },
+    HkdfTest {
+        salt: &[],
+        ikm: &[
+            0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c,
+            0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c,
+            0x0c, 0x0c, 0x0c, 0x0c, 0x0c, 0x0c,
+        ],
+        info: &[],
+        prk: &[
+            0x2a, 0xdc, 0xca, 0xda, 0x18, 0x77, 0x9e, 0x7c,
+            0x20, 0x77, 0xad, 0x2e, 0xb1, 0x9d, 0x3f, 0x3e,
+            0x73, 0x13, 0x85, 0xdd,
+        ],
+        okm: &[
+            0x2c, 0x91, 0x11, 0x72, 0x04, 0xd7, 0x45, 0xf3,
+            0x50, 0x0d, 0x63, 0x6a, 0x62, 0xf6, 0x4f, 0x0a,
+            0xb3, 0xba, 0xe5, 0x48, 0xaa, 0x53, 0xd4, 0x23,
+            0xb0, 0xd1, 0xf2, 0x7e, 0xbb, 0xa6, 0xf5, 0xe5,
+            0x67, 0x3a, 0x08, 0x1d, 0x70, 0xcc, 0xe7, 0xac,
+            0xfc, 0x48,
+        ],
+    },
+];
+
+/// PBKDF2 test vector
+pub struct Pbkdf2Test {
+    pub password: &'static [u8],
+    pub salt: &'static [u8],
+    pub rounds: usize,
+    pub output: &'static [u8],
+}
+
+/// PBKDF2-HMAC-SHA1 test vectors from RFC 6070 (test case with 2^24
+/// rounds is omitted)
+pub const PBKDF2_HMAC_SHA1: &[Pbkdf2Test] = &[
+    Pbkdf2Test {
+        password: &[
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x6f, 0x72, 0x64,
+        ],
+        salt: &[
+            0x73, 0x61, 0x6c, 0x74,
+        ],
+        rounds: 1,
+        output: &[
+            0x0c, 0x60, 0xc8, 0x0f, 0x96, 0x1f, 0x0e, 0x71,
+            0xf3, 0xa9, 0xb5, 0x24, 0xaf, 0x60, 0x12, 0x06,
+            0x2f, 0xe0, 0x37, 0xa6,
+        ],
+    },
+    Pbkdf2Test {
+        password: &[
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x6f, 0x72, 0x64,
+        ],
+        salt: &[
+            0x73, 0x61, 0x6c, 0x74,
+        ],
+        rounds: 2,
+        output: &[
+            0xea, 0x6c, 0x01, 0x4d, 0xc7, 0x2d, 0x6f, 0x8c,
+            0xcd, 0x1e, 0xd9, 0x2a, 0xce, 0x1d, 0x41, 0xf0,
+            0xd8, 0xde, 0x89, 0x57,
+        ],
+    },
+    Pbkdf2Test {
+        password: &[
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x6f, 0x72, 0x64,
+        ],
+        salt: &[
+            0x73, 0x61, 0x6c, 0x74,
+        ],
+        rounds: 4096,
+        output: &[
+            0x4b, 0x00, 0x79, 0x01, 0xb7, 0x65, 0x48, 0x9a,
+            0xbe, 0xad, 0x49, 0xd9, 0x26, 0xf7, 0x21, 0xd0,
+            0x65, 0xa4, 0x29, 0xc1,
+        ],
+    },
+    Pbkdf2Test {
+        password: &[
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x6f, 0x72, 0x64,
+            0x50, 0x41, 0x53, 0x53, 0x57, 0x4f, 0x52, 0x44,
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x6f, 0x72, 0x64,
+        ],
+        salt: &[
+            0x73, 0x61, 0x6c, 0x74, 0x53, 0x41, 0x4c, 0x54,
+            0x73, 0x61, 0x6c, 0x74, 0x53, 0x41, 0x4c, 0x54,
+            0x73, 0x61, 0x6c, 0x74, 0x53, 0x41, 0x4c, 0x54,
+            0x73, 0x61, 0x6c, 0x74, 0x53, 0x41, 0x4c, 0x54,
+            0x73, 0x61, 0x6c, 0x74,
+        ],
+        rounds: 4096,
+        output: &[
+            0x3d, 0x2e, 0xec, 0x4f, 0xe4, 0x1c, 0x84, 0x9b,
+            0x80, 0xc8, 0xd8, 0x36, 0x62, 0xc0, 0xe4, 0x4a,
+            0x8b, 0x29, 0x1a, 0x96, 0x4c, 0xf2, 0xf0, 0x70,
+            0x38,
+        ],
+    },
+    Pbkdf2Test {
+        password: &[
+            0x70, 0x61, 0x73, 0x73, 0x00, 0x77, 0x6f, 0x72,
+            0x64,
+        ],
+        salt: &[
+            0x73, 0x61, 0x00, 0x6c, 0x74,
+        ],
+        rounds: 4096,
+        output: &[
+            0x56, 0xfa, 0x6a, 0xa7, 0x55, 0x48, 0x09, 0x9d,
+            0xcc, 0x37, 0xd7, 0xf0, 0x34, 0x25, 0xe0, 0xc3,
+        ],
+    },
+];
+
+/// PBKDF2-HMAC-SHA256 test vectors from RFC 7914, section 11
+pub const PBKDF2_HMAC_SHA256: &[Pbkdf2Test] = &[
+    Pbkdf2Test {
+        password: &[
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x64,
+        ],
+        salt: &[
+            0x73, 0x61, 0x6c, 0x74,
+        ],
+        rounds: 1,
+        output: &[
+            0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f,
+            0xec, 0x16, 0x91, 0xc2, 0x25, 0x44, 0xb6, 0x05,
+            0xf9, 0x41, 0x85, 0x21, 0x6d, 0xde, 0x04, 0x65,
+            0xe6, 0x8b, 0x9d, 0x57, 0xc2, 0x0d, 0xac, 0xbc,
+            0x49, 0xca, 0x9c, 0xcc, 0xf1, 0x79, 0xb6, 0x45,
+            0x99, 0x16, 0x64, 0xb3, 0x9d, 0x77, 0xef, 0x31,
+            0x7c, 0x71, 0xb8, 0x45, 0xb1, 0xe3, 0x0b, 0xd5,
+            0x09, 0x11, 0x20, 0x41, 0xd3, 0xa1, 0x97, 0x83,
+        ],
+    },
+    Pbkdf2Test {
+        password: &[
+            0x50, 0x61, 0x73, 0x73, 0x77, 0x6f, 0x72, 0x64,
+        ],
+        salt: &[
+            0x4e, 0x61, 0x43, 0x6c,
+        ],
+        rounds: 80000,
+        output: &[
+            0x4d, 0xdc, 0xd8, 0xf6, 0x0b, 0x98, 0xbe, 0x21,
+            0x83, 0x0c, 0xee, 0x5e, 0xf2, 0x27, 0x01, 0xf9,
+            0x64, 0x1a, 0x44, 0x18, 0xd0, 0x4c, 0x04, 0x14,
+            0xae, 0xff, 0x08, 0x87, 0x6b, 0x34, 0xab, 0x56,
+            0xa1, 0xd4, 0x25, 0xa1, 0x22, 0x58, 0x33, 0x54,
+            0x9a, 0xdb, 0x84, 0x1b, 0x51, 0xc9, 0xb3, 0x17,
+            0x6a, 0x27, 0x2b, 0xde, 0xbb, 0xa1, 0xd0, 0x78,
+            0x47, 0x8f, 0x62, 0xb3, 0x97, 0xf3, 0x3c, 0x8d,
+        ],
+    },
+];
+
+/// KBKDF test vector
+pub struct KbkdfTest {
+    pub mode: KbkdfMode<'static>,
+    pub counter_len: usize,
+    pub counter_location: CounterLocation,
+    pub key: &'static [u8],
+    pub fixed_input: &'static [u8],
+    pub output: &'static [u8],
+}
+
+/// KBKDF-CMAC-AES128 test vector from NIST CAVP (counter mode)
+pub const KBKDF_CMAC_AES128: &[KbkdfTest] = &[
+    KbkdfTest {
+        mode: KbkdfMode::Counter,
+        counter_len: 1,
+        counter_location: CounterLocation::BeforeFixed,
+        key: &[
+            0xdf, 0xf1, 0xe5, 0x0a, 0xc0, 0xb6, 0x9d, 0xc4,
+            0x0f, 0x10, 0x51, 0xd4, 0x6c, 0x2b, 0x06, 0x9c,
+        ],
+        fixed_input: &[
+            0xc1, 0x6e, 0x6e, 0x02, 0xc5, 0xa3, 0xdc, 0xc8,
+            0xd7, 0x8b, 0x9a, 0xc1, 0x30, 0x68, 0x77, 0x76,
+            0x13, 0x10, 0x45, 0x5b, 0x4e, 0x41, 0x46, 0x99,
+            0x51, 0xd9, 0xe6, 0xc2, 0x24, 0x5a, 0x06, 0x4b,
+            0x33, 0xfd, 0x8c, 0x3b, 0x01, 0x20, 0x3a, 0x78,
+            0x24, 0x48, 0x5b, 0xf0, 0xa6, 0x40, 0x60, 0xc4,
+            0x64, 0x8b, 0x70, 0x7d, 0x26, 0x07, 0x93, 0x56,
+            0x99, 0x31, 0x6e, 0xa5,
+        ],
+        output: &[
+            0x8b, 0xe8, 0xf0, 0x86, 0x9b, 0x3c, 0x0b, 0xa9,
+            0x7b, 0x71, 0x86, 0x3d, 0x1b, 0x9f, 0x78, 0x13,
+        ],
+    },
+];
+
+/// KBKDF-HMAC-SHA1 test vector from NIST CAVP (counter mode)
+pub const KBKDF_HMAC_SHA1: &[KbkdfTest] = &[
+    KbkdfTest {
+        mode: KbkdfMode::Counter,
+        counter_len: 1,
+        counter_location: CounterLocation::BeforeFixed,
+        key: &[
+            0x00, 0xa3, 0x9b, 0xd5, 0x47, 0xfb, 0x88, 0xb2,
+            0xd9, 0x87, 0x27, 0xcf, 0x64, 0xc1, 0x95, 0xc6,
+            0x1e, 0x1c, 0xad, 0x6c,
+        ],
+        fixed_input: &[
+            0x98, 0x13, 0x2c, 0x1f, 0xfa, 0xf5, 0x9a, 0xe5,
+            0xcb, 0xc0, 0xa3, 0x13, 0x3d, 0x84, 0xc5, 0x51,
+            0xbb, 0x97, 0xe0, 0xc7, 0x5e, 0xca, 0xdd, 0xfc,
+            0x30, 0x05, 0x6f, 0x68, 0x76, 0xf5, 0x98, 0x03,
+            0x00, 0x9b, 0xff, 0xc7, 0xd7, 0x5c, 0x4e, 0xd4,
+            0x6f, 0x40, 0xb8, 0xf8, 0x04, 0x26, 0x75, 0x0d,
+            0x15, 0xbc, 0x1d, 0xdb, 0x14, 0xac, 0x5d, 0xcb,
+            0x69, 0xa6, 0x82, 0x42,
+        ],
+        output: &[
+            0x06, 0x11, 0xe1, 0x90, 0x36, 0x09, 0xb4, 0x7a,
+            0xd7, 0xa5, 0xfc, 0x2c, 0x82, 0xe4, 0x77, 0x02,
+        ],
+    },
+];
+
+/// KBKDF-HMAC-SHA256 test vectors
+///
+/// Only the first vector is from NIST CAVP. The other vectors cover the
+/// remaining modes and counter configurations. Counter mode vectors match
+/// pyca/cryptography and the feedback mode vector with IV matches OpenSSL.
+/// Feedback mode without counter and double-pipeline vectors were checked
+/// only against an independent implementation of SP 800-108 and should be
+/// replaced with the CAVP `KDFFeedback` and `KDFDblPipeline` entries.
+pub const KBKDF_HMAC_SHA256: &[KbkdfTest] = &[
+    KbkdfTest {
+        mode: KbkdfMode::Counter,
+        counter_len: 1,
+        counter_location: CounterLocation::BeforeFixed,
+        key: &[
+            0x3e, 0xdc, 0x6b, 0x5b, 0x8f, 0x7a, 0xad, 0xbd,
+            0x71, 0x37, 0x32, 0xb4, 0x82, 0xb8, 0xf9, 0x79,
+            0x28, 0x6e, 0x1e, 0xa3, 0xb8, 0xf8, 0xf9, 0x9c,
+            0x30, 0xc8, 0x84, 0xcf, 0xe3, 0x34, 0x9b, 0x83,
+        ],
+        fixed_input: &[
+            0x98, 0xe9, 0x98, 0x8b, 0xb4, 0xcc, 0x8b, 0x34,
+            0xd7, 0x92, 0x2e, 0x1c, 0x68, 0xad, 0x69, 0x2b,
+            0xa2, 0xa1, 0xd9, 0xae, 0x15, 0x14, 0x95, 0x71,
+            0x67, 0x5f, 0x17, 0xa7, 0x7a, 0xd4, 0x9e, 0x80,
+            0xc8, 0xd2, 0xa8, 0x5e, 0x83, 0x1a, 0x26, 0x44,
+            0x5b, 0x1f, 0x0f, 0xf4, 0x4d, 0x70, 0x84, 0xa1,
+            0x72, 0x06, 0xb4, 0x89, 0x6c, 0x81, 0x12, 0xda,
+            0xad, 0x18, 0x60, 0x5a,
+        ],
+        output: &[
+            0x6c, 0x03, 0x76, 0x52, 0x99, 0x06, 0x74, 0xa0,
+            0x78, 0x44, 0x73, 0x2d, 0x0a, 0xd9, 0x85, 0xf9,
+        ],
+    },
+    KbkdfTest {
+        mode: KbkdfMode::Counter,
+        counter_len: 4,
+        counter_location: CounterLocation::AfterFixed,
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        fixed_input: &[
+            0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b,
+            0x6c, 0x6d, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x73,
+            0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b,
+            0x7c, 0x7d, 0x7e, 0x7f, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b,
+            0x8c, 0x8d, 0x8e, 0x8f, 0x90, 0x91, 0x92, 0x93,
+            0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b,
+            0x9c, 0x9d, 0x9e, 0x9f,
+        ],
+        output: &[
+            0x25, 0x56, 0x1a, 0x0b, 0x5f, 0x7e, 0x15, 0x83,
+            0xa6, 0x24, 0x21, 0xd3, 0x9b, 0x87, 0xca, 0xd7,
+            0x03, 0xa4, 0xea, 0x9f, 0x35, 0xd8, 0xa4, 0x65,
+            0x06, 0xa5, 0x67, 0x59, 0x4c, 0xa7, 0x60, 0x91,
+            0xdf, 0x6b, 0xa7, 0xd0, 0xff, 0x79, 0x7a, 0xa8,
+            0x70, 0x57, 0xa9, 0xf3, 0x4e, 0x34, 0x33, 0x0d,
+            0xf6, 0x7b, 0x04, 0x37, 0x63, 0x0d, 0x8a, 0x6b,
+            0x01, 0x0e, 0xfc, 0xd4, 0x03, 0xfa, 0xca, 0xa0,
+            0xb9, 0x89, 0x53, 0xa6, 0x8e, 0x8e, 0xa9, 0xe7,
+            0xe0, 0x54, 0xc8, 0xf0, 0x67, 0xe9, 0x7e, 0xa9,
+        ],
+    },
+    KbkdfTest {
+        mode: KbkdfMode::Counter,
+        counter_len: 2,
+        counter_location: CounterLocation::MiddleFixed(20),
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        fixed_input: &[
+            0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b,
+            0x6c, 0x6d, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x73,
+            0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b,
+            0x7c, 0x7d, 0x7e, 0x7f, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b,
+            0x8c, 0x8d, 0x8e, 0x8f, 0x90, 0x91, 0x92, 0x93,
+            0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b,
+            0x9c, 0x9d, 0x9e, 0x9f,
+        ],
+        output: &[
+            0x4c, 0x15, 0xec, 0xf2, 0x83, 0xd1, 0x48, 0xe8,
+            0x64, 0xd9, 0x52, 0x0c, 0xdb, 0x47, 0x0c, 0x86,
+            0xa8, 0x27, 0x38, 0xaa, 0xda, 0x83, 0xdb, 0xc1,
+            0xcb, 0x56, 0x4c, 0x05, 0xfd, 0x54, 0x81, 0x1f,
+            0xf9, 0x9b, 0xdc, 0xc0, 0x7b, 0x42, 0x36, 0x70,
+            0x2c, 0x16, 0xc6, 0x0b, 0x5a, 0xfd, 0xcc, 0xae,
+            0xac, 0x80, 0xc4, 0x34, 0xa3, 0x58, 0x5e, 0x97,
+            0x49, 0x93, 0x9f, 0xf4, 0x80, 0xf2, 0x49, 0xd1,
+            0x8a, 0xcb, 0x04, 0x54, 0x42, 0x21, 0x85, 0xf3,
+            0x89, 0x80, 0x90, 0x50, 0xd6, 0x20, 0xf8, 0x8a,
+        ],
+    },
+    KbkdfTest {
+        mode: KbkdfMode::Feedback(&[
+            0xc8, 0xc9, 0xca, 0xcb, 0xcc, 0xcd, 0xce, 0xcf,
+            0xd0, 0xd1, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7,
+            0xd8, 0xd9, 0xda, 0xdb, 0xdc, 0xdd, 0xde, 0xdf,
+            0xe0, 0xe1, 0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7,
+        ]),
+        counter_len: 4,
+        counter_location: CounterLocation::BeforeFixed,
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        fixed_input: &[
+            0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b,
+            0x6c, 0x6d, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x73,
+            0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b,
+            0x7c, 0x7d, 0x7e, 0x7f, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b,
+            0x8c, 0x8d, 0x8e, 0x8f, 0x90, 0x91, 0x92, 0x93,
+            0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b,
+            0x9c, 0x9d, 0x9e, 0x9f,
+        ],
+        output: &[
+            0x98, 0x0e, 0x2e, 0x24, 0xbb, 0x61, 0x59, 0xef,
+            0xbf, 0xc1, 0x21, 0xef, 0x4d, 0xb8, 0x40, 0x70,
+            0x9b, 0x48, 0x9b, 0xa7, 0x4d, 0xfa, 0x6e, 0xcd,
+            0xf9, 0x2b, 0xfc, 0x12, 0xa7, 0x1b, 0x5b, 0x28,
+            0xd6, 0x77, 0x04, 0x99, 0xc1, 0x0e, 0x86, 0x90,
+            0xb9, 0xc9, 0xa7, 0x02, 0xf9, 0xc3, 0xfc, 0x29,
+            0xa3, 0x21, 0x14, 0x6f, 0x6b, 0xa2, 0x9b, 0x4a,
+            0xc3, 0xbd, 0x93, 0x36, 0x09, 0xe8, 0xa4, 0xce,
+            0x3b, 0xa1, 0x4f, 0xb7, 0xc4, 0xac, 0x4f, 0xfb,
+            0xca, 0x76, 0x1f, 0x4f, 0x3e, 0xc4, 0x92, 0x8b,
+        ],
+    },
+    KbkdfTest {
+        mode: KbkdfMode::Feedback(&[]),
+        counter_len: 0,
+        counter_location: CounterLocation::None,
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        fixed_input: &[
+            0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b,
+            0x6c, 0x6d, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x73,
+            0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b,
+            0x7c, 0x7d, 0x7e, 0x7f, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b,
+            0x8c, 0x8d, 0x8e, 0x8f, 0x90, 0x91, 0x92, 0x93,
+            0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b,
+            0x9c, 0x9d, 0x9e, 0x9f,
+        ],
+        output: &[
+            0x02, 0x52, 0x23, 0x05, 0xc5, 0x59, 0xed, 0xa7,
+            0x80, 0xe4, 0x76, 0x05, 0xce, 0x34, 0xf4, 0xf5,
+            0x29, 0xd7, 0x5f, 0xac, 0x22, 0x34, 0x3a, 0x49,
+            0x64, 0xa5, 0x8e, 0xf2, 0x04, 0x52, 0x28, 0x11,
+            0x94, 0xca, 0xa6, 0x6a, 0x20, 0x71, 0xa8, 0x48,
+        ],
+    },
+    KbkdfTest {
+        mode: KbkdfMode::DoublePipeline,
+        counter_len: 1,
+        counter_location: CounterLocation::BeforeIter,
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        fixed_input: &[
+            0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b,
+            0x6c, 0x6d, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x73,
+            0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b,
+            0x7c, 0x7d, 0x7e, 0x7f, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b,
+            0x8c, 0x8d, 0x8e, 0x8f, 0x90, 0x91, 0x92, 0x93,
+            0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b,
+            0x9c, 0x9d, 0x9e, 0x9f,
+        ],
+        output: &[
+            0xfe, 0x57, 0x1a, 0xd2, 0xf0, 0xad, 0xfd, 0xa1,
+            0xbc, 0x90, 0x24, 0x8f, 0x3a, 0x16, 0x56, 0xbc,
+            0x93, 0x6a, 0x39, 0x99, 0x20, 0x77, 0xc8, 0x52,
+            0x28, 0xbd, 0x31, 0x80, 0x55, 0x70, 0x88, 0xeb,
+            0x86, 0x35, 0x2e, 0x75, 0x15, 0xb5, 0xc5, 0xfe,
+            0xd7, 0x48, 0x4e, 0x28, 0x65, 0xc2, 0x8d, 0xfa,
+            0x59, 0xd7, 0x16, 0x95, 0xb3, 0x84, 0xa2, 0xa6,
+            0x45, 0xf4, 0x0c, 0xa6, 0x47, 0xfe, 0x59, 0xa8,
+            0x2d, 0x41, 0x76, 0x00, 0x84, 0x85, 0x30, 0x32,
+            0x53, 0xb1, 0xa2, 0x92, 0xb6, 0x7e, 0xfb, 0xc4,
+        ],
+    },
+    KbkdfTest {
+        mode: KbkdfMode::DoublePipeline,
+        counter_len: 0,
+        counter_location: CounterLocation::None,
+        key: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f,
+        ],
+        fixed_input: &[
+            0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0x6a, 0x6b,
+            0x6c, 0x6d, 0x6e, 0x6f, 0x70, 0x71, 0x72, 0x73,
+            0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x7b,
+            0x7c, 0x7d, 0x7e, 0x7f, 0x80, 0x81, 0x82, 0x83,
+            0x84, 0x85, 0x86, 0x87, 0x88, 0x89, 0x8a, 0x8b,
+            0x8c, 0x8d, 0x8e, 0x8f, 0x90, 0x91, 0x92, 0x93,
+            0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0x9b,
+            0x9c, 0x9d, 0x9e, 0x9f,
+        ],
+        output: &[
+            0x94, 0xca, 0xa6, 0x6a, 0x20, 0x71, 0xa8, 0x48,
+            0x9d, 0xf9, 0x8a, 0x65, 0x27, 0x33, 0xf0, 0x5d,
+            0x81, 0xf0, 0x68, 0x79, 0x86, 0xc1, 0x3c, 0x8a,
+            0x96, 0x5e, 0x5a, 0xe3, 0x08, 0x10, 0xa8, 0xbd,
+            0xe6, 0x87, 0xa5, 0xef, 0x48, 0x83, 0xa6, 0xd8,
+        ],
+    },
+];
+
+/// One-step KDF test vector
+pub struct OneStepTest {
+    /// Salt used as MAC key, used only by `concat_kdf_mac` tests
+    pub salt: &'static [u8],
+    pub z: &'static [u8],
+    pub info: &'static [u8],
+    pub output: &'static [u8],
+}
+
+/// ANSI X9.63 KDF with SHA-1 test vector from NIST CAVP
+pub const X963_SHA1: &[OneStepTest] = &[
+    OneStepTest {
+        salt: &[],
+        z: &[
+            0x1c, 0x7d, 0x7b, 0x5f, 0x05, 0x97, 0xb0, 0x3d,
+            0x06, 0xa0, 0x18, 0x46, 0x6e, 0xd1, 0xa9, 0x3e,
+            0x30, 0xed, 0x4b, 0x04, 0xdc, 0x64, 0xcc, 0xdd,
+        ],
+        info: &[],
+        output: &[
+            0xbf, 0x71, 0xdf, 0xfd, 0x8f, 0x4d, 0x99, 0x22,
+            0x39, 0x36, 0xbe, 0xb4, 0x6f, 0xee, 0x8c, 0xcc,
+        ],
+    },
+];
+
+/// ANSI X9.63 KDF with SHA-256 test vectors from NIST CAVP
+pub const X963_SHA256: &[OneStepTest] = &[
+    OneStepTest {
+        salt: &[],
+        z: &[
+            0x96, 0xc0, 0x56, 0x19, 0xd5, 0x6c, 0x32, 0x8a,
+            0xb9, 0x5f, 0xe8, 0x4b, 0x18, 0x26, 0x4b, 0x08,
+            0x72, 0x5b, 0x85, 0xe3, 0x3f, 0xd3, 0x4f, 0x08,
+        ],
+        info: &[],
+        output: &[
+            0x44, 0x30, 0x24, 0xc3, 0xda, 0xe6, 0x6b, 0x95,
+            0xe6, 0xf5, 0x67, 0x06, 0x01, 0x55, 0x8f, 0x71,
+        ],
+    },
+    OneStepTest {
+        salt: &[],
+        z: &[
+            0x22, 0x51, 0x8b, 0x10, 0xe7, 0x0f, 0x2a, 0x3f,
+            0x24, 0x38, 0x10, 0xae, 0x32, 0x54, 0x13, 0x9e,
+            0xfb, 0xee, 0x04, 0xaa, 0x57, 0xc7, 0xaf, 0x7d,
+        ],
+        info: &[
+            0x75, 0xee, 0xf8, 0x1a, 0xa3, 0x04, 0x1e, 0x33,
+            0xb8, 0x09, 0x71, 0x20, 0x3d, 0x2c, 0x0c, 0x52,
+        ],
+        output: &[
+            0xc4, 0x98, 0xaf, 0x77, 0x16, 0x1c, 0xc5, 0x9f,
+            0x29, 0x62, 0xb9, 0xa7, 0x13, 0xe2, 0xb2, 0x15,
+            0x15, 0x2d, 0x13, 0x97, 0x66, 0xce, 0x34, 0xa7,
+            0x76, 0xdf, 0x11, 0x86, 0x6a, 0x69, 0xbf, 0x2e,
+            0x52, 0xa1, 0x3d, 0x9c, 0x7c, 0x6f, 0xc8, 0x78,
+            0xc5, 0x0c, 0x5e, 0xa0, 0xbc, 0x7b, 0x00, 0xe0,
+            0xda, 0x24, 0x47, 0xcf, 0xd8, 0x74, 0xf6, 0xcf,
+            0x92, 0xf3, 0x0d, 0x00, 0x97, 0x11, 0x14, 0x85,
+            0x50, 0x0c, 0x90, 0xc3, 0xaf, 0x8b, 0x48, 0x78,
+            0x72, 0xd0, 0x46, 0x85, 0xd1, 0x4c, 0x8d, 0x1d,
+            0xc8, 0xd7, 0xfa, 0x08, 0xbe, 0xb0, 0xce, 0x0a,
+            0xba, 0xbc, 0x11, 0xf0, 0xbd, 0x49, 0x62, 0x69,
+            0x14, 0x2d, 0x43, 0x52, 0x5a, 0x78, 0xe5, 0xbc,
+            0x79, 0xa1, 0x7f, 0x59, 0x67, 0x6a, 0x57, 0x06,
+            0xdc, 0x54, 0xd5, 0x4d, 0x4d, 0x1f, 0x0b, 0xd7,
+            0xe3, 0x86, 0x12, 0x8e, 0xc2, 0x6a, 0xfc, 0x21,
+        ],
+    },
+];
+
+/// One-step KDF with SHA-256 test vectors generated with pyca/cryptography
+pub const CONCAT_KDF_SHA256: &[OneStepTest] = &[
+    OneStepTest {
+        salt: &[],
+        z: &[
+            0x52, 0x16, 0x9a, 0xf5, 0xc4, 0x85, 0xdc, 0xc2,
+            0x32, 0x1e, 0xb8, 0xd2, 0x6d, 0x5e, 0xfa, 0x21,
+            0xfb, 0x9b, 0x93, 0xc9, 0x8e, 0x38, 0x41, 0x2e,
+            0xe2, 0x48, 0x4c, 0xf1, 0x4f, 0x0d, 0x0d, 0x23,
+        ],
+        info: &[
+            0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0x37, 0x28, 0x15,
+            0x7e, 0x63, 0x46, 0x12, 0xc1, 0x2d, 0x6d, 0x52,
+            0x23, 0xe2, 0x04, 0xae, 0xea, 0x43, 0x41, 0x56,
+            0x53, 0x69, 0x64, 0x7b, 0xd1, 0x84, 0xbc, 0xd2,
+            0x46, 0xf7, 0x29, 0x71, 0xf2, 0x92, 0xba, 0xda,
+            0xa2, 0xfe, 0x41, 0x24, 0x61, 0x2c, 0xba,
+        ],
+        output: &[
+            0x1c, 0x3b, 0xc9, 0xe7, 0xc4, 0x54, 0x7c, 0x51,
+            0x91, 0xc0, 0xd4, 0x78, 0xcc, 0xca, 0xed, 0x55,
+        ],
+    },
+    OneStepTest {
+        salt: &[],
+        z: &[
+            0x52, 0x16, 0x9a, 0xf5, 0xc4, 0x85, 0xdc, 0xc2,
+            0x32, 0x1e, 0xb8, 0xd2, 0x6d, 0x5e, 0xfa, 0x21,
+            0xfb, 0x9b, 0x93, 0xc9, 0x8e, 0x38, 0x41, 0x2e,
+            0xe2, 0x48, 0x4c, 0xf1, 0x4f, 0x0d, 0x0d, 0x23,
+        ],
+        info: &[
+            0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0x37, 0x28, 0x15,
+            0x7e, 0x63, 0x46, 0x12, 0xc1, 0x2d, 0x6d, 0x52,
+            0x23, 0xe2, 0x04, 0xae, 0xea, 0x43, 0x41, 0x56,
+            0x53, 0x69, 0x64, 0x7b, 0xd1, 0x84, 0xbc, 0xd2,
+            0x46, 0xf7, 0x29, 0x71, 0xf2, 0x92, 0xba, 0xda,
+            0xa2, 0xfe, 0x41, 0x24, 0x61, 0x2c, 0xba,
+        ],
+        output: &[
+            0x1c, 0x3b, 0xc9, 0xe7, 0xc4, 0x54, 0x7c, 0x51,
+            0x91, 0xc0, 0xd4, 0x78, 0xcc, 0xca, 0xed, 0x55,
+            0x9a, 0xe0, 0x45, 0xdb, 0xb2, 0xca, 0x04, 0x37,
+            0x8e, 0x11, 0x6f, 0xe4, 0x9b, 0x3b, 0xa5, 0x23,
+            0xa2, 0x92, 0x62, 0x6a, 0xfb, 0x33, 0x0b, 0xfd,
+            0xfe, 0xca, 0x37, 0xfa, 0x7d, 0xc1, 0x5b, 0x37,
+            0x1e, 0x20, 0x1a, 0xfb, 0x8b, 0x72, 0xbb, 0xf2,
+            0x1b, 0xfe, 0xd2, 0xd4, 0xd2, 0x8f, 0xbd, 0x04,
+            0x0e, 0xed, 0xab, 0x76, 0x43, 0xea, 0x3b, 0xb7,
+            0xaf, 0x77, 0x73, 0x48, 0xc4, 0x89, 0x20, 0xf7,
+            0x4e, 0xcc, 0x81, 0xd3, 0x87, 0x5e, 0x87, 0xc8,
+            0xab, 0xe8, 0x5e, 0x1e, 0xa7, 0xe6, 0x66, 0xd6,
+            0x00, 0x3f, 0x88, 0xb7,
+        ],
+    },
+];
+
+/// One-step KDF with HMAC-SHA256 test vectors generated with
+/// pyca/cryptography, the first vector uses the default salt
+pub const CONCAT_KDF_HMAC_SHA256: &[OneStepTest] = &[
+    OneStepTest {
+        salt: &[],
+        z: &[
+            0x52, 0x16, 0x9a, 0xf5, 0xc4, 0x85, 0xdc, 0xc2,
+            0x32, 0x1e, 0xb8, 0xd2, 0x6d, 0x5e, 0xfa, 0x21,
+            0xfb, 0x9b, 0x93, 0xc9, 0x8e, 0x38, 0x41, 0x2e,
+            0xe2, 0x48, 0x4c, 0xf1, 0x4f, 0x0d, 0x0d, 0x23,
+        ],
+        info: &[
+            0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0x37, 0x28, 0x15,
+            0x7e, 0x63, 0x46, 0x12, 0xc1, 0x2d, 0x6d, 0x52,
+            0x23, 0xe2, 0x04, 0xae, 0xea, 0x43, 0x41, 0x56,
+            0x53, 0x69, 0x64, 0x7b, 0xd1, 0x84, 0xbc, 0xd2,
+            0x46, 0xf7, 0x29, 0x71, 0xf2, 0x92, 0xba, 0xda,
+            0xa2, 0xfe, 0x41, 0x24, 0x61, 0x2c, 0xba,
+        ],
+        output: &[
+            0xe2, 0x0f, 0xd3, 0xcc, 0x1a, 0x8c, 0xcf, 0x25,
+            0x90, 0x6c, 0x57, 0x8f, 0x07, 0x2a, 0x21, 0x80,
+            0x66, 0x8f, 0x7d, 0xf0, 0x86, 0x8e, 0xde, 0x90,
+            0xfc, 0xff, 0xa4, 0x92, 0x8d, 0xca, 0x41, 0x0a,
+            0x95, 0x51, 0x1c, 0x94, 0x43, 0xc3, 0xa2, 0x04,
+            0x87, 0xe5, 0xae, 0x44, 0x1d, 0xae, 0xbd, 0x44,
+            0x43, 0x0b, 0x8d, 0xf3, 0xa5, 0x37, 0xea, 0x68,
+            0xc2, 0x26, 0xdd, 0x51, 0x49, 0x18, 0x41, 0x8a,
+            0x62, 0xa0, 0x0d, 0xab, 0xd3, 0x6c, 0x52, 0xef,
+            0x1a, 0xdc, 0xf1, 0x2c, 0x64, 0x41, 0x81, 0x71,
+            0xdd, 0x69, 0x09, 0xd6, 0x87, 0xa7, 0x65, 0x2a,
+            0xed, 0xd9, 0x84, 0x36, 0x7d, 0x39, 0x34, 0xc3,
+            0x70, 0x3c, 0xbc, 0xd4,
+        ],
+    },
+    OneStepTest {
+        salt: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+            0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            0x10, 0x11, 0x12, 0x13,
+        ],
+        z: &[
+            0x52, 0x16, 0x9a, 0xf5, 0xc4, 0x85, 0xdc, 0xc2,
+            0x32, 0x1e, 0xb8, 0xd2, 0x6d, 0x5e, 0xfa, 0x21,
+            0xfb, 0x9b, 0x93, 0xc9, 0x8e, 0x38, 0x41, 0x2e,
+            0xe2, 0x48, 0x4c, 0xf1, 0x4f, 0x0d, 0x0d, 0x23,
+        ],
+        info: &[
+            0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0x37, 0x28, 0x15,
+            0x7e, 0x63, 0x46, 0x12, 0xc1, 0x2d, 0x6d, 0x52,
+            0x23, 0xe2, 0x04, 0xae, 0xea, 0x43, 0x41, 0x56,
+            0x53, 0x69, 0x64, 0x7b, 0xd1, 0x84, 0xbc, 0xd2,
+            0x46, 0xf7, 0x29, 0x71, 0xf2, 0x92, 0xba, 0xda,
+            0xa2, 0xfe, 0x41, 0x24, 0x61, 0x2c, 0xba,
+        ],
+        output: &[
+            0xb1, 0xc7, 0x54, 0x52, 0xee, 0x45, 0x23, 0x53,
+            0xce, 0x79, 0xb5, 0xd7, 0xf5, 0xfd, 0x07, 0x41,
+            0x6f, 0x12, 0x1c, 0x93, 0x03, 0x96, 0x99, 0x3b,
+            0x64, 0x18, 0xed, 0x94, 0xe6, 0xbb, 0x10, 0xcc,
+            0xb0, 0x6a, 0x19, 0x98, 0xed, 0x68, 0x47, 0x40,
+            0xd9, 0xe0, 0x49, 0xcf, 0xfb, 0xd3, 0x78, 0x77,
+        ],
+    },
+];
+
+/// TLS 1.2 PRF test vector
+pub struct Tls12PrfTest {
+    pub secret: &'static [u8],
+    pub label: &'static [u8],
+    pub seed: &'static [u8],
+    pub output: &'static [u8],
+}
+
+/// TLS 1.2 PRF with SHA-256 test vector from the IETF TLS WG mailing list
+pub const TLS12_PRF_SHA256: &[Tls12PrfTest] = &[
+    Tls12PrfTest {
+        secret: &[
+            0x9b, 0xbe, 0x43, 0x6b, 0xa9, 0x40, 0xf0, 0x17,
+            0xb1, 0x76, 0x52, 0x84, 0x9a, 0x71, 0xdb, 0x35,
+        ],
+        label: b"test label",
+        seed: &[
+            0xa0, 0xba, 0x9f, 0x93, 0x6c, 0xda, 0x31, 0x18,
+            0x27, 0xa6, 0xf7, 0x96, 0xff, 0xd5, 0x19, 0x8c,
+        ],
+        output: &[
+            0xe3, 0xf2, 0x29, 0xba, 0x72, 0x7b, 0xe1, 0x7b,
+            0x8d, 0x12, 0x26, 0x20, 0x55, 0x7c, 0xd4, 0x53,
+            0xc2, 0xaa, 0xb2, 0x1d, 0x07, 0xc3, 0xd4, 0x95,
+            0x32, 0x9b, 0x52, 0xd4, 0xe6, 0x1e, 0xdb, 0x5a,
+            0x6b, 0x30, 0x17, 0x91, 0xe9, 0x0d, 0x35, 0xc9,
+            0xc9, 0xa4, 0x6b, 0x4e, 0x14, 0xba, 0xf9, 0xaf,
+            0x0f, 0xa0, 0x22, 0xf7, 0x07, 0x7d, 0xef, 0x17,
+            0xab, 0xfd, 0x37, 0x97, 0xc0, 0x56, 0x4b, 0xab,
+            0x4f, 0xbc, 0x91, 0x66, 0x6e, 0x9d, 0xef, 0x9b,
+            0x97, 0xfc, 0xe3, 0x4f, 0x79, 0x67, 0x89, 0xba,
+            0xa4, 0x80, 0x82, 0xd1, 0x22, 0xee, 0x42, 0xc5,
+            0xa7, 0x2e, 0x5a, 0x51, 0x10, 0xff, 0xf7, 0x01,
+            0x87, 0x34, 0x7b, 0x66,
+        ],
+    },
+];
+
+/// TLS 1.2 PRF with SHA-384 test vector from the IETF TLS WG mailing list
+pub const TLS12_PRF_SHA384: &[Tls12PrfTest] = &[
+    Tls12PrfTest {
+        secret: &[
+            0xb8, 0x0b, 0x73, 0x3d, 0x6c, 0xee, 0xfc, 0xdc,
+            0x71, 0x56, 0x6e, 0xa4, 0x8e, 0x55, 0x67, 0xdf,
+        ],
+        label: b"test label",
+        seed: &[
+            0xcd, 0x66, 0x5c, 0xf6, 0xa8, 0x44, 0x7d, 0xd6,
+            0xff, 0x8b, 0x27, 0x55, 0x5e, 0xdb, 0x74, 0x65,
+        ],
+        output: &[
+            0x7b, 0x0c, 0x18, 0xe9, 0xce, 0xd4, 0x10, 0xed,
+            0x18, 0x04, 0xf2, 0xcf, 0xa3, 0x4a, 0x33, 0x6a,
+            0x1c, 0x14, 0xdf, 0xfb, 0x49, 0x00, 0xbb, 0x5f,
+            0xd7, 0x94, 0x21, 0x07, 0xe8, 0x1c, 0x83, 0xcd,
+            0xe9, 0xca, 0x0f, 0xaa, 0x60, 0xbe, 0x9f, 0xe3,
+            0x4f, 0x82, 0xb1, 0x23, 0x3c, 0x91, 0x46, 0xa0,
+            0xe5, 0x34, 0xcb, 0x40, 0x0f, 0xed, 0x27, 0x00,
+            0x88, 0x4f, 0x9d, 0xc2, 0x36, 0xf8, 0x0e, 0xdd,
+            0x8b, 0xfa, 0x96, 0x11, 0x44, 0xc9, 0xe8, 0xd7,
+            0x92, 0xec, 0xa7, 0x22, 0xa7, 0xb3, 0x2f, 0xc3,
+            0xd4, 0x16, 0xd4, 0x73, 0xeb, 0xc2, 0xc5, 0xfd,
+            0x4a, 0xbf, 0xda, 0xd0, 0x5d, 0x91, 0x84, 0x25,
+            0x9b, 0x5b, 0xf8, 0xcd, 0x4d, 0x90, 0xfa, 0x0d,
+            0x31, 0xe2, 0xde, 0xc4, 0x79, 0xe4, 0xf1, 0xa2,
+            0x60, 0x66, 0xf2, 0xee, 0xa9, 0xa6, 0x92, 0x36,
+            0xa3, 0xe5, 0x26, 0x55, 0xc9, 0xe9, 0xae, 0xe6,
+            0x91, 0xc8, 0xf3, 0xa2, 0x68, 0x54, 0x30, 0x8d,
+            0x5e, 0xaa, 0x3b, 0xe8, 0x5e, 0x09, 0x90, 0x70,
+            0x3d, 0x73, 0xe5, 0x6f,
+        ],
+    },
+];
+
+/// TLS 1.3 key schedule test vector
+///
+/// Empty `psk` and `shared_secret` are passed as `None`.
+pub struct Tls13Test {
+    pub psk: &'static [u8],
+    pub shared_secret: &'static [u8],
+    pub early_secret: &'static [u8],
+    /// Hash of messages from `ClientHello` to `ServerHello`
+    pub handshake_hash: &'static [u8],
+    pub handshake_secret: &'static [u8],
+    pub client_hs_secret: &'static [u8],
+    pub server_hs_secret: &'static [u8],
+    pub client_hs_key: &'static [u8],
+    pub client_hs_iv: &'static [u8],
+    pub server_hs_key: &'static [u8],
+    pub server_hs_iv: &'static [u8],
+    pub client_finished_key: &'static [u8],
+    pub server_finished_key: &'static [u8],
+    pub master_secret: &'static [u8],
+    /// Hash of messages from `ClientHello` to client `Finished`
+    pub resumption_hash: &'static [u8],
+    pub res_master_secret: &'static [u8],
+    pub ticket_nonce: &'static [u8],
+    pub resumption_psk: &'static [u8],
+    /// Early secret computed from `resumption_psk`
+    pub res_early_secret: &'static [u8],
+    /// Resumption binder key derived from `res_early_secret`
+    pub res_binder_key: &'static [u8],
+}
+
+/// TLS 1.3 key schedule test vector from RFC 8448, section 3 (simple 1-RTT
+/// handshake), resumption early secret and binder key are from section 4
+/// (resumed 0-RTT handshake)
+pub const TLS13_SHA256: &[Tls13Test] = &[
+    Tls13Test {
+        psk: &[],
+        shared_secret: &[
+            0x8b, 0xd4, 0x05, 0x4f, 0xb5, 0x5b, 0x9d, 0x63,
+            0xfd, 0xfb, 0xac, 0xf9, 0xf0, 0x4b, 0x9f, 0x0d,
+            0x35, 0xe6, 0xd6, 0x3f, 0x53, 0x75, 0x63, 0xef,
+            0xd4, 0x62, 0x72, 0x90, 0x0f, 0x89, 0x49, 0x2d,
+        ],
+        early_secret: &[
+            0x33, 0xad, 0x0a, 0x1c, 0x60, 0x7e, 0xc0, 0x3b,
+            0x09, 0xe6, 0xcd, 0x98, 0x93, 0x68, 0x0c, 0xe2,
+            0x10, 0xad, 0xf3, 0x00, 0xaa, 0x1f, 0x26, 0x60,
+            0xe1, 0xb2, 0x2e, 0x10, 0xf1, 0x70, 0xf9, 0x2a,
+        ],
+        handshake_hash: &[
+            0x86, 0x0c, 0x06, 0xed, 0xc0, 0x78, 0x58, 0xee,
+            0x8e, 0x78, 0xf0, 0xe7, 0x42, 0x8c, 0x58, 0xed,
+            0xd6, 0xb4, 0x3f, 0x2c, 0xa3, 0xe6, 0xe9, 0x5f,
+            0x02, 0xed, 0x06, 0x3c, 0xf0, 0xe1, 0xca, 0xd8,
+        ],
+        handshake_secret: &[
+            0x1d, 0xc8, 0x26, 0xe9, 0x36, 0x06, 0xaa, 0x6f,
+            0xdc, 0x0a, 0xad, 0xc1, 0x2f, 0x74, 0x1b, 0x01,
+            0x04, 0x6a, 0xa6, 0xb9, 0x9f, 0x69, 0x1e, 0xd2,
+            0x21, 0xa9, 0xf0, 0xca, 0x04, 0x3f, 0xbe, 0xac,
+        ],
+        client_hs_secret: &[
+            0xb3, 0xed, 0xdb, 0x12, 0x6e, 0x06, 0x7f, 0x35,
+            0xa7, 0x80, 0xb3, 0xab, 0xf4, 0x5e, 0x2d, 0x8f,
+            0x3b, 0x1a, 0x95, 0x07, 0x38, 0xf5, 0x2e, 0x96,
+            0x00, 0x74, 0x6a, 0x0e, 0x27, 0xa5, 0x5a, 0x21,
+        ],
+        server_hs_secret: &[
+            0xb6, 0x7b, 0x7d, 0x69, 0x0c, 0xc1, 0x6c, 0x4e,
+            0x75, 0xe5, 0x42, 0x13, 0xcb, 0x2d, 0x37, 0xb4,
+            0xe9, 0xc9, 0x12, 0xbc, 0xde, 0xd9, 0x10, 0x5d,
+            0x42, 0xbe, 0xfd, 0x59, 0xd3, 0x91, 0xad, 0x38,
+        ],
+        client_hs_key: &[
+            0xdb, 0xfa, 0xa6, 0x93, 0xd1, 0x76, 0x2c, 0x5b,
+            0x66, 0x6a, 0xf5, 0xd9, 0x50, 0x25, 0x8d, 0x01,
+        ],
+        client_hs_iv: &[
+            0x5b, 0xd3, 0xc7, 0x1b, 0x83, 0x6e, 0x0b, 0x76,
+            0xbb, 0x73, 0x26, 0x5f,
+        ],
+        server_hs_key: &[
+            0x3f, 0xce, 0x51, 0x60, 0x09, 0xc2, 0x17, 0x27,
+            0xd0, 0xf2, 0xe4, 0xe8, 0x6e, 0xe4, 0x03, 0xbc,
+        ],
+        server_hs_iv: &[
+            0x5d, 0x31, 0x3e, 0xb2, 0x67, 0x12, 0x76, 0xee,
+            0x13, 0x00, 0x0b, 0x30,
+        ],
+        client_finished_key: &[
+            0xb8, 0x0a, 0xd0, 0x10, 0x15, 0xfb, 0x2f, 0x0b,
+            0xd6, 0x5f, 0xf7, 0xd4, 0xda, 0x5d, 0x6b, 0xf8,
+            0x3f, 0x84, 0x82, 0x1d, 0x1f, 0x87, 0xfd, 0xc7,
+            0xd3, 0xc7, 0x5b, 0x5a, 0x7b, 0x42, 0xd9, 0xc4,
+        ],
+        server_finished_key: &[
+            0x00, 0x8d, 0x3b, 0x66, 0xf8, 0x16, 0xea, 0x55,
+            0x9f, 0x96, 0xb5, 0x37, 0xe8, 0x85, 0xc3, 0x1f,
+            0xc0, 0x68, 0xbf, 0x49, 0x2c, 0x65, 0x2f, 0x01,
+            0xf2, 0x88, 0xa1, 0xd8, 0xcd, 0xc1, 0x9f, 0xc8,
+        ],
+        master_secret: &[
+            0x18, 0xdf, 0x06, 0x84, 0x3d, 0x13, 0xa0, 0x8b,
+            0xf2, 0xa4, 0x49, 0x84, 0x4c, 0x5f, 0x8a, 0x47,
+            0x80, 0x01, 0xbc, 0x4d, 0x4c, 0x62, 0x79, 0x84,
+            0xd5, 0xa4, 0x1d, 0xa8, 0xd0, 0x40, 0x29, 0x19,
+        ],
+        resumption_hash: &[
+            0x20, 0x91, 0x45, 0xa9, 0x6e, 0xe8, 0xe2, 0xa1,
+            0x22, 0xff, 0x81, 0x00, 0x47, 0xcc, 0x95, 0x26,
+            0x84, 0x65, 0x8d, 0x60, 0x49, 0xe8, 0x64, 0x29,
+            0x42, 0x6d, 0xb8, 0x7c, 0x54, 0xad, 0x14, 0x3d,
+        ],
+        res_master_secret: &[
+            0x7d, 0xf2, 0x35, 0xf2, 0x03, 0x1d, 0x2a, 0x05,
+            0x12, 0x87, 0xd0, 0x2b, 0x02, 0x41, 0xb0, 0xbf,
+            0xda, 0xf8, 0x6c, 0xc8, 0x56, 0x23, 0x1f, 0x2d,
+            0x5a, 0xba, 0x46, 0xc4, 0x34, 0xec, 0x19, 0x6c,
+        ],
+        ticket_nonce: &[
+            0x00, 0x00,
+        ],
+        resumption_psk: &[
+            0x4e, 0xcd, 0x0e, 0xb6, 0xec, 0x3b, 0x4d, 0x87,
+            0xf5, 0xd6, 0x02, 0x8f, 0x92, 0x2c, 0xa4, 0xc5,
+            0x85, 0x1a, 0x27, 0x7f, 0xd4, 0x13, 0x11, 0xc9,
+            0xe6, 0x2d, 0x2c, 0x94, 0x92, 0xe1, 0xc4, 0xf3,
+        ],
+        res_early_secret: &[
+            0x9b, 0x21, 0x88, 0xe9, 0xb2, 0xfc, 0x6d, 0x64,
+            0xd7, 0x1d, 0xc3, 0x29, 0x90, 0x0e, 0x20, 0xbb,
+            0x41, 0x91, 0x50, 0x00, 0xf6, 0x78, 0xaa, 0x83,
+            0x9c, 0xbb, 0x79, 0x7c, 0xb7, 0xd8, 0x33, 0x2c,
+        ],
+        res_binder_key: &[
+            0x69, 0xfe, 0x13, 0x1a, 0x3b, 0xba, 0xd5, 0xd6,
+            0x3c, 0x64, 0xee, 0xbc, 0xc3, 0x0e, 0x39, 0x5b,
+            0x9d, 0x81, 0x07, 0x72, 0x6a, 0x13, 0xd0, 0x74,
+            0xe3, 0x89, 0xdb, 0xc8, 0xa4, 0xe4, 0x72, 0x56,
+        ],
+    },
+];
+
+/// TLS 1.3 traffic keys test vector
+pub struct Tls13TrafficKeysTest {
+    pub secret: &'static [u8],
+    pub key: &'static [u8],
+    pub iv: &'static [u8],
+}
+
+/// TLS 1.3 application traffic keys test vectors from RFC 8448, section 3,
+/// secrets are `client_application_traffic_secret_0` and
+/// `server_application_traffic_secret_0`
+pub const TLS13_TRAFFIC_KEYS_SHA256: &[Tls13TrafficKeysTest] = &[
+    Tls13TrafficKeysTest {
+        secret: &[
+            0x9e, 0x40, 0x64, 0x6c, 0xe7, 0x9a, 0x7f, 0x9d,
+            0xc0, 0x5a, 0xf8, 0x88, 0x9b, 0xce, 0x65, 0x52,
+            0x87, 0x5a, 0xfa, 0x0b, 0x06, 0xdf, 0x00, 0x87,
+            0xf7, 0x92, 0xeb, 0xb7, 0xc1, 0x75, 0x04, 0xa5,
+        ],
+        key: &[
+            0x17, 0x42, 0x2d, 0xda, 0x59, 0x6e, 0xd5, 0xd9,
+            0xac, 0xd8, 0x90, 0xe3, 0xc6, 0x3f, 0x50, 0x51,
+        ],
+        iv: &[
+            0x5b, 0x78, 0x92, 0x3d, 0xee, 0x08, 0x57, 0x90,
+            0x33, 0xe5, 0x23, 0xd9,
+        ],
+    },
+    Tls13TrafficKeysTest {
+        secret: &[
+            0xa1, 0x1a, 0xf9, 0xf0, 0x55, 0x31, 0xf8, 0x56,
+            0xad, 0x47, 0x11, 0x6b, 0x45, 0xa9, 0x50, 0x32,
+            0x82, 0x04, 0xb4, 0xf4, 0x4b, 0xfb, 0x6b, 0x3a,
+            0x4b, 0x4f, 0x1f, 0x3f, 0xcb, 0x63, 0x16, 0x43,
+        ],
+        key: &[
+            0x9f, 0x02, 0x28, 0x3b, 0x6c, 0x9c, 0x07, 0xef,
+            0xc2, 0x6b, 0xb9, 0xf2, 0xac, 0x92, 0xe3, 0x56,
+        ],
+        iv: &[
+            0xcf, 0x78, 0x2b, 0x88, 0xdd, 0x83, 0x54, 0x9a,
+            0xad, 0xf1, 0xe9, 0x84,
+        ],
+    },
+];
+
+/// Run HKDF tests using the provided test vectors.
+///
+/// Empty salt is passed as `None`, which is equivalent to `HashLen` zeros.
+pub fn run_hkdf_test<D>(tests: &[HkdfTest])
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    let mut buf = [0u8; 255*64 + 1];
+    for t in tests.iter() {
+        let salt = if t.salt.is_empty() { None } else { Some(t.salt) };
+        let okm = &mut buf[..t.okm.len()];
+
+        let (prk, hkdf) = Hkdf::<D>::extract(salt, t.ikm);
+        assert_eq!(&prk[..], t.prk);
+        hkdf.expand(t.info, okm).unwrap();
+        assert_eq!(okm, t.okm);
+
+        let hkdf = Hkdf::<D>::from_prk(t.prk).unwrap();
+        for b in okm.iter_mut() { *b = 0; }
+        hkdf.expand(t.info, okm).unwrap();
+        assert_eq!(okm, t.okm);
+
+        // test that info split into several parts gives the same result
+        for i in 0..t.info.len() + 1 {
+            let (a, b) = t.info.split_at(i);
+            for b in okm.iter_mut() { *b = 0; }
+            hkdf.expand_multi_info(&[a, b], okm).unwrap();
+            assert_eq!(okm, t.okm);
+        }
+
+        for b in okm.iter_mut() { *b = 0; }
+        Hkdf::<D>::derive(salt, t.ikm, t.info, okm).unwrap();
+        assert_eq!(okm, t.okm);
+    }
+
+    // test output length limit
+    let n = D::OutputSize::to_usize();
+    if 255*n < buf.len() {
+        let hkdf = Hkdf::<D>::new(None, &[]);
+        assert!(hkdf.expand(&[], &mut buf[..255*n]).is_ok());
+        let res = hkdf.expand(&[], &mut buf[..255*n + 1]);
+        assert_eq!(res, Err(InvalidLength));
+    }
+}
+
+/// Run PBKDF2 tests using the provided test vectors.
+pub fn run_pbkdf2_test<M: Mac + Clone>(tests: &[Pbkdf2Test]) {
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let res = &mut buf[..t.output.len()];
+        pbkdf2::<M>(t.password, t.salt, t.rounds, res).unwrap();
+        assert_eq!(res, t.output);
+    }
+}
+
+/// Run parallel PBKDF2 tests using the provided test vectors.
+#[cfg(feature = "parallel")]
+pub fn run_pbkdf2_par_test<M: Mac + Clone + Sync>(tests: &[Pbkdf2Test]) {
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let res = &mut buf[..t.output.len()];
+        pbkdf2_par::<M>(t.password, t.salt, t.rounds, res).unwrap();
+        assert_eq!(res, t.output);
+    }
+}
+
+/// Run KBKDF tests using the provided test vectors.
+pub fn run_kbkdf_test<M: Mac + Clone>(tests: &[KbkdfTest]) {
+    let mut buf = [0u8; 256*64];
+    for t in tests.iter() {
+        let kdf = Kbkdf::<M>::new(t.key, t.counter_len, t.counter_location)
+            .unwrap();
+        let okm = &mut buf[..t.output.len()];
+        kdf.derive(t.mode, &[t.fixed_input], okm).unwrap();
+        assert_eq!(okm, t.output);
+
+        // test that fixed input data split into several parts gives
+        // the same result
+        for i in 0..t.fixed_input.len() + 1 {
+            let (a, b) = t.fixed_input.split_at(i);
+            for b in okm.iter_mut() { *b = 0; }
+            kdf.derive(t.mode, &[a, b], okm).unwrap();
+            assert_eq!(okm, t.output);
+        }
+    }
+
+    // test output length limit for 8 bit counter
+    let n = M::OutputSize::to_usize();
+    if 256*n <= buf.len() {
+        let location = CounterLocation::AfterFixed;
+        let kdf = Kbkdf::<M>::new(tests[0].key, 1, location).unwrap();
+        let mode = KbkdfMode::Counter;
+        assert!(kdf.derive(mode, &[], &mut buf[..255*n]).is_ok());
+        let res = kdf.derive(mode, &[], &mut buf[..255*n + 1]);
+        assert_eq!(res, Err(KbkdfError::InvalidLength));
+    }
+
+    // test rejection of invalid configurations
+    let okm = &mut buf[..n];
+    let kdf = Kbkdf::<M>::new(tests[0].key, 4, CounterLocation::None)
+        .unwrap();
+    let res = kdf.derive(KbkdfMode::Counter, &[], okm);
+    assert_eq!(res, Err(KbkdfError::CounterRequired));
+    let location = CounterLocation::MiddleFixed(3);
+    let kdf = Kbkdf::<M>::new(tests[0].key, 4, location).unwrap();
+    assert!(kdf.derive(KbkdfMode::Counter, &[&[1], &[2, 3]], okm).is_ok());
+    let res = kdf.derive(KbkdfMode::Counter, &[&[1], &[2]], okm);
+    assert_eq!(res, Err(KbkdfError::InvalidCounterPosition));
+
+    // test rejection of invalid counter width
+    let location = CounterLocation::BeforeFixed;
+    for &counter_len in [0, 5].iter() {
+        let res = Kbkdf::<M>::new(tests[0].key, counter_len, location);
+        assert_eq!(res.err(), Some(KbkdfError::InvalidCounterLength));
+    }
+    assert!(Kbkdf::<M>::new(tests[0].key, 0, CounterLocation::None).is_ok());
+}
+
+/// Run ANSI X9.63 KDF tests using the provided test vectors.
+pub fn run_x963_test<D: Digest>(tests: &[OneStepTest]) {
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let okm = &mut buf[..t.output.len()];
+        x963_kdf::<D>(t.z, t.info, okm).unwrap();
+        assert_eq!(okm, t.output);
+    }
+}
+
+/// Run one-step KDF tests using the provided test vectors.
+pub fn run_concat_kdf_test<D: Digest>(tests: &[OneStepTest]) {
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let okm = &mut buf[..t.output.len()];
+        concat_kdf::<D>(t.z, t.info, okm).unwrap();
+        assert_eq!(okm, t.output);
+    }
+}
+
+/// Run one-step KDF with MAC tests using the provided test vectors.
+pub fn run_concat_kdf_mac_test<M: Mac + Clone>(tests: &[OneStepTest]) {
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let mac = M::new_varkey(t.salt).unwrap();
+        let okm = &mut buf[..t.output.len()];
+        concat_kdf_mac(&mac, t.z, t.info, okm).unwrap();
+        assert_eq!(okm, t.output);
+    }
+}
+
+/// Run TLS 1.2 PRF tests using the provided test vectors.
+pub fn run_tls12_prf_test<M: Mac + Clone>(tests: &[Tls12PrfTest]) {
+    let mut buf = [0u8; 1024];
+    for t in tests.iter() {
+        let out = &mut buf[..t.output.len()];
+        tls::tls12_prf::<M>(t.secret, t.label, t.seed, out).unwrap();
+        assert_eq!(out, t.output);
+    }
+}
+
+/// Run TLS 1.3 key schedule tests using the provided test vectors.
+pub fn run_tls13_test<D>(tests: &[Tls13Test])
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    fn opt(val: &[u8]) -> Option<&[u8]> {
+        if val.is_empty() { None } else { Some(val) }
+    }
+
+    let mut key = [0u8; 64];
+    let mut iv = [0u8; 64];
+    for t in tests.iter() {
+        let early = tls::EarlySecret::<D>::new(opt(t.psk));
+        assert_eq!(&early.secret()[..], t.early_secret);
+
+        let hs = early.into_handshake_secret(opt(t.shared_secret));
+        assert_eq!(&hs.secret()[..], t.handshake_secret);
+        let client = hs.client_handshake_traffic_secret(t.handshake_hash);
+        assert_eq!(&client[..], t.client_hs_secret);
+        let server = hs.server_handshake_traffic_secret(t.handshake_hash);
+        assert_eq!(&server[..], t.server_hs_secret);
+
+        let keys = [
+            (&client, t.client_hs_key, t.client_hs_iv),
+            (&server, t.server_hs_key, t.server_hs_iv),
+        ];
+        for &(secret, k, i) in keys.iter() {
+            let (key, iv) = (&mut key[..k.len()], &mut iv[..i.len()]);
+            tls::traffic_keys::<D>(secret, key, iv).unwrap();
+            assert_eq!(key, k);
+            assert_eq!(iv, i);
+        }
+        let fk = tls::finished_key::<D>(&client);
+        assert_eq!(&fk[..], t.client_finished_key);
+        let fk = tls::finished_key::<D>(&server);
+        assert_eq!(&fk[..], t.server_finished_key);
+
+        let master = hs.into_master_secret();
+        assert_eq!(&master.secret()[..], t.master_secret);
+        let res = master.resumption_master_secret(t.resumption_hash);
+        assert_eq!(&res[..], t.res_master_secret);
+        let psk = tls::resumption_psk::<D>(&res, t.ticket_nonce);
+        assert_eq!(&psk[..], t.resumption_psk);
+
+        let early = tls::EarlySecret::<D>::new(Some(t.resumption_psk));
+        assert_eq!(&early.secret()[..], t.res_early_secret);
+        let res_binder = early.res_binder_key();
+        assert_eq!(&res_binder[..], t.res_binder_key);
+        // external binder key differs from resumption one only by label
+        let empty = D::default().finalize_fixed();
+        let ext_binder = early.ext_binder_key();
+        let expected = tls::derive_secret::<D>(
+            early.secret(), b"ext binder", &empty);
+        assert_eq!(ext_binder, expected);
+        assert_ne!(ext_binder, res_binder);
+    }
+}
+
+/// Run TLS 1.3 traffic keys tests using the provided test vectors.
+pub fn run_tls13_traffic_keys_test<D>(tests: &[Tls13TrafficKeysTest])
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    let mut key = [0u8; 64];
+    let mut iv = [0u8; 64];
+    for t in tests.iter() {
+        let secret = GenericArray::from_slice(t.secret);
+        let (key, iv) = (&mut key[..t.key.len()], &mut iv[..t.iv.len()]);
+        tls::traffic_keys::<D>(secret, key, iv).unwrap();
+        assert_eq!(key, t.key);
+        assert_eq!(iv, t.iv);
+
+        let next = tls::next_traffic_secret::<D>(secret);
+        let mut expected = tls::Secret::<D>::default();
+        tls::hkdf_expand_label::<D>(secret, b"traffic upd", &[], &mut expected)
+            .unwrap();
+        assert_eq!(next, expected);
+        assert_ne!(&next[..], t.secret);
+    }
+}
+
+/// Define HKDF test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_hkdf_test!(hkdf_sha256, Sha256, HKDF_SHA256);
+/// ```
+#[macro_export]
+macro_rules! new_hkdf_test {
+    ($name:ident, $hash:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_hkdf_test, $tests};
+
+            run_hkdf_test::<$hash>($tests);
+        }
+    }
+}
+
+/// Define PBKDF2 test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_pbkdf2_test!(pbkdf2_hmac_sha1, Hmac<Sha1>, PBKDF2_HMAC_SHA1);
+/// ```
+#[macro_export]
+macro_rules! new_pbkdf2_test {
+    ($name:ident, $mac:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_pbkdf2_test, $tests};
+
+            run_pbkdf2_test::<$mac>($tests);
+        }
+    }
+}
+
+/// Define parallel PBKDF2 test, requires enabled `parallel` feature
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_pbkdf2_par_test!(pbkdf2_hmac_sha1, Hmac<Sha1>, PBKDF2_HMAC_SHA1);
+/// ```
+#[cfg(feature = "parallel")]
+#[macro_export]
+macro_rules! new_pbkdf2_par_test {
+    ($name:ident, $mac:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_pbkdf2_par_test, $tests};
+
+            run_pbkdf2_par_test::<$mac>($tests);
+        }
+    }
+}
+
+/// Define KBKDF test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_kbkdf_test!(kbkdf_hmac_sha256, Hmac<Sha256>, KBKDF_HMAC_SHA256);
+/// ```
+#[macro_export]
+macro_rules! new_kbkdf_test {
+    ($name:ident, $mac:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_kbkdf_test, $tests};
+
+            run_kbkdf_test::<$mac>($tests);
+        }
+    }
+}
+
+/// Define ANSI X9.63 KDF test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_x963_test!(x963_sha256, Sha256, X963_SHA256);
+/// ```
+#[macro_export]
+macro_rules! new_x963_test {
+    ($name:ident, $hash:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_x963_test, $tests};
+
+            run_x963_test::<$hash>($tests);
+        }
+    }
+}
+
+/// Define one-step KDF test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_concat_kdf_test!(concat_kdf_sha256, Sha256, CONCAT_KDF_SHA256);
+/// ```
+#[macro_export]
+macro_rules! new_concat_kdf_test {
+    ($name:ident, $hash:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_concat_kdf_test, $tests};
+
+            run_concat_kdf_test::<$hash>($tests);
+        }
+    }
+}
+
+/// Define one-step KDF with MAC test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_concat_kdf_mac_test!(
+///     concat_kdf_hmac_sha256, Hmac<Sha256>, CONCAT_KDF_HMAC_SHA256
+/// );
+/// ```
+#[macro_export]
+macro_rules! new_concat_kdf_mac_test {
+    ($name:ident, $mac:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_concat_kdf_mac_test, $tests};
+
+            run_concat_kdf_mac_test::<$mac>($tests);
+        }
+    }
+}
+
+/// Define TLS 1.2 PRF test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_tls12_prf_test!(tls12_prf_sha256, Hmac<Sha256>, TLS12_PRF_SHA256);
+/// ```
+#[macro_export]
+macro_rules! new_tls12_prf_test {
+    ($name:ident, $mac:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_tls12_prf_test, $tests};
+
+            run_tls12_prf_test::<$mac>($tests);
+        }
+    }
+}
+
+/// Define TLS 1.3 key schedule test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_tls13_test!(tls13_sha256, Sha256, TLS13_SHA256);
+/// ```
+#[macro_export]
+macro_rules! new_tls13_test {
+    ($name:ident, $hash:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_tls13_test, $tests};
+
+            run_tls13_test::<$hash>($tests);
+        }
+    }
+}
+
+/// Define TLS 1.3 traffic keys test
+///
+/// Usage example:
+///
+/// ```rust,ignore
+/// kdf::new_tls13_traffic_keys_test!(
+///     tls13_traffic_keys_sha256, Sha256, TLS13_TRAFFIC_KEYS_SHA256
+/// );
+/// ```
+#[macro_export]
+macro_rules! new_tls13_traffic_keys_test {
+    ($name:ident, $hash:ty, $tests:ident) => {
+        #[test]
+        fn $name() {
+            use kdf::dev::{run_tls13_traffic_keys_test, $tests};
+
+            run_tls13_traffic_keys_test::<$hash>($tests);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+use crypto_mac::InvalidKey;
+
+/// Error type which is returned if requested output length exceeds
+/// the maximum supported by KDF
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InvalidLength;
+
+/// Error type which is returned if pseudorandom key is too short
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InvalidPrkLength;
+
+/// Error type which is returned by `Kbkdf`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum KbkdfError {
+    /// Requested output length exceeds the maximum supported by KDF
+    InvalidLength,
+    /// Counter mode is used without counter
+    CounterRequired,
+    /// `MiddleFixed` counter position is bigger than length of fixed input
+    /// data
+    InvalidCounterPosition,
+    /// Counter width is not in the range from 1 to 4 bytes
+    InvalidCounterLength,
+    /// Key-derivation key is not accepted by the PRF
+    InvalidKey(InvalidKey),
+}
+
+impl From<InvalidKey> for KbkdfError {
+    fn from(err: InvalidKey) -> KbkdfError {
+        KbkdfError::InvalidKey(err)
+    }
+}
+
+impl fmt::Display for InvalidLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid output length")
+    }
+}
+
+impl fmt::Display for InvalidPrkLength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid pseudorandom key length")
+    }
+}
+
+impl fmt::Display for KbkdfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            KbkdfError::InvalidLength => f.write_str("invalid output length"),
+            KbkdfError::CounterRequired =>
+                f.write_str("counter is required in counter mode"),
+            KbkdfError::InvalidCounterPosition => f.write_str(
+                "counter position is bigger than fixed input data length"),
+            KbkdfError::InvalidCounterLength => f.write_str(
+                "counter width must be in the range from 1 to 4 bytes"),
+            KbkdfError::InvalidKey(ref err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for InvalidLength {
+    fn description(&self) -> &str {
+        "invalid output length"
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for InvalidPrkLength {
+    fn description(&self) -> &str {
+        "invalid pseudorandom key length"
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for KbkdfError {
+    fn description(&self) -> &str {
+        match *self {
+            KbkdfError::InvalidLength => "invalid output length",
+            KbkdfError::CounterRequired =>
+                "counter is required in counter mode",
+            KbkdfError::InvalidCounterPosition =>
+                "counter position is bigger than fixed input data length",
+            KbkdfError::InvalidCounterLength =>
+                "counter width must be in the range from 1 to 4 bytes",
+            KbkdfError::InvalidKey(_) => "invalid key",
+        }
+    }
+}
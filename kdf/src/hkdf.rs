@@ -0,0 +1,110 @@
+use crypto_mac::{Hmac, Mac};
+use digest::{BlockInput, FixedOutput, Input};
+use digest::generic_array::GenericArray;
+use digest::generic_array::typenum::Unsigned;
+use errors::{InvalidLength, InvalidPrkLength};
+
+/// [HMAC-based Extract-and-Expand Key Derivation Function][1] (HKDF)
+/// generic over hash function.
+///
+/// Instance of this type holds the pseudorandom key (PRK) produced by the
+/// extract step and can be used for expanding it into several output keys.
+///
+/// [1]: https://tools.ietf.org/html/rfc5869
+#[derive(Clone)]
+pub struct Hkdf<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    /// HMAC instance keyed with PRK
+    hmac: Hmac<D>,
+}
+
+impl<D> Hkdf<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    /// Perform the extract step and return PRK together with HKDF instance
+    /// initialized with it.
+    ///
+    /// If `salt` is `None`, string of `HashLen` zeros is used.
+    pub fn extract(salt: Option<&[u8]>, ikm: &[u8])
+        -> (GenericArray<u8, D::OutputSize>, Self)
+    {
+        // HMAC pads key with zeros, so empty key is equivalent to
+        // `HashLen` zeros
+        let mut hmac = Hmac::<D>::new_varkey(salt.unwrap_or(&[]))
+            .expect("HMAC accepts keys of any length");
+        hmac.input(ikm);
+        let prk = hmac.finalize().code();
+        let hkdf = Self::from_prk(&prk).expect("PRK has valid length");
+        (prk, hkdf)
+    }
+
+    /// Perform the extract step and return HKDF instance.
+    pub fn new(salt: Option<&[u8]>, ikm: &[u8]) -> Self {
+        Self::extract(salt, ikm).1
+    }
+
+    /// Create HKDF instance from already extracted PRK, skipping the extract
+    /// step.
+    ///
+    /// Returns `Err(InvalidPrkLength)` if `prk` is shorter than `HashLen`.
+    pub fn from_prk(prk: &[u8]) -> Result<Self, InvalidPrkLength> {
+        if prk.len() < D::OutputSize::to_usize() {
+            return Err(InvalidPrkLength);
+        }
+        let hmac = Hmac::<D>::new_varkey(prk)
+            .expect("HMAC accepts keys of any length");
+        Ok(Hkdf { hmac })
+    }
+
+    /// Perform the expand step filling `okm` with output key material.
+    ///
+    /// Returns `Err(InvalidLength)` if length of `okm` is bigger than
+    /// `255*HashLen`.
+    pub fn expand(&self, info: &[u8], okm: &mut [u8])
+        -> Result<(), InvalidLength>
+    {
+        self.expand_multi_info(&[info], okm)
+    }
+
+    /// Perform the expand step using concatenation of `info` components as
+    /// context and application specific information.
+    ///
+    /// Returns `Err(InvalidLength)` if length of `okm` is bigger than
+    /// `255*HashLen`.
+    pub fn expand_multi_info(&self, info: &[&[u8]], okm: &mut [u8])
+        -> Result<(), InvalidLength>
+    {
+        let n = D::OutputSize::to_usize();
+        if okm.len() > 255*n {
+            return Err(InvalidLength);
+        }
+
+        let mut prev: Option<GenericArray<u8, D::OutputSize>> = None;
+        for (i, chunk) in okm.chunks_mut(n).enumerate() {
+            let mut hmac = self.hmac.clone();
+            if let Some(ref prev) = prev {
+                hmac.input(prev);
+            }
+            for part in info {
+                hmac.input(part);
+            }
+            hmac.input(&[i as u8 + 1]);
+            let block = hmac.finalize().code();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+            prev = Some(block);
+        }
+        Ok(())
+    }
+
+    /// Perform both extract and expand steps filling `okm` with output key
+    /// material.
+    ///
+    /// Returns `Err(InvalidLength)` if length of `okm` is bigger than
+    /// `255*HashLen`.
+    pub fn derive(salt: Option<&[u8]>, ikm: &[u8], info: &[u8], okm: &mut [u8])
+        -> Result<(), InvalidLength>
+    {
+        Self::new(salt, ikm).expand(info, okm)
+    }
+}
@@ -0,0 +1,223 @@
+use crypto_mac::Mac;
+use digest::generic_array::GenericArray;
+use digest::generic_array::typenum::Unsigned;
+use errors::KbkdfError;
+
+/// Mode of KBKDF operation
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KbkdfMode<'a> {
+    /// Counter mode, PRF input consists only of counter and fixed input data
+    Counter,
+    /// Feedback mode, output of the previous iteration is included into PRF
+    /// input, the contained value is used as IV for the first iteration
+    /// (can be empty)
+    Feedback(&'a [u8]),
+    /// Double-pipeline iteration mode, the first pipeline computes secret
+    /// values from fixed input data which are included into PRF input
+    /// in the second pipeline
+    DoublePipeline,
+}
+
+/// Location of the counter in PRF input
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CounterLocation {
+    /// Counter precedes iteration variable (in counter mode it's equivalent
+    /// to `BeforeFixed`)
+    BeforeIter,
+    /// Counter is placed right before fixed input data
+    BeforeFixed,
+    /// Counter is placed right after fixed input data
+    AfterFixed,
+    /// Counter is inserted into fixed input data after the given number
+    /// of bytes
+    MiddleFixed(usize),
+    /// Counter is not used (not allowed in counter mode)
+    None,
+}
+
+/// [NIST SP 800-108][1] key-based key derivation function (KBKDF) generic
+/// over `Mac` used as PRF.
+///
+/// Counter is encoded as a big endian integer with width from 1 to 4 bytes.
+///
+/// [1]: https://doi.org/10.6028/NIST.SP.800-108
+#[derive(Clone)]
+pub struct Kbkdf<M: Mac + Clone> {
+    prf: M,
+    counter_len: usize,
+    counter_location: CounterLocation,
+}
+
+#[inline(always)]
+fn to_be_bytes(n: u32) -> [u8; 4] {
+    [(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8]
+}
+
+/// Feed `parts` of fixed input data into `prf` inserting `counter` after
+/// `pos` bytes.
+///
+/// Position must not be bigger than total length of `parts`.
+fn input_fixed<M: Mac>(
+    prf: &mut M, parts: &[&[u8]], counter: Option<(usize, &[u8])>,
+) {
+    let (mut pos, counter) = match counter {
+        Some((pos, counter)) => (pos, counter),
+        None => {
+            for part in parts { prf.input(part); }
+            return;
+        },
+    };
+    let mut inserted = false;
+    for part in parts {
+        if !inserted && pos <= part.len() {
+            let (a, b) = part.split_at(pos);
+            prf.input(a);
+            prf.input(counter);
+            prf.input(b);
+            inserted = true;
+        } else {
+            if !inserted { pos -= part.len(); }
+            prf.input(part);
+        }
+    }
+    if !inserted {
+        debug_assert_eq!(pos, 0);
+        prf.input(counter);
+    }
+}
+
+impl<M: Mac + Clone> Kbkdf<M> {
+    /// Create new KBKDF instance from key-derivation key, counter width in
+    /// bytes and counter location.
+    ///
+    /// Returns `Err(KbkdfError::InvalidKey)` if `M` does not accept `key`
+    /// and `Err(KbkdfError::InvalidCounterLength)` if `counter_len` is not
+    /// in the range from 1 to 4 while counter is used.
+    pub fn new(key: &[u8], counter_len: usize, location: CounterLocation)
+        -> Result<Self, KbkdfError>
+    {
+        Self::from_mac(M::new_varkey(key)?, counter_len, location)
+    }
+
+    /// Create new KBKDF instance from `Mac` initialized with key-derivation
+    /// key.
+    ///
+    /// Returns `Err(KbkdfError::InvalidCounterLength)` if `counter_len` is
+    /// not in the range from 1 to 4 while counter is used.
+    pub fn from_mac(prf: M, counter_len: usize, location: CounterLocation)
+        -> Result<Self, KbkdfError>
+    {
+        let counter_len = if location == CounterLocation::None {
+            0
+        } else if 1 <= counter_len && counter_len <= 4 {
+            counter_len
+        } else {
+            return Err(KbkdfError::InvalidCounterLength);
+        };
+        Ok(Kbkdf { prf, counter_len, counter_location: location })
+    }
+
+    /// Derive key material into `okm` using concatenation of `fixed_input`
+    /// parts as fixed input data.
+    ///
+    /// Returns `Err(KbkdfError::InvalidLength)` if the number of PRF output
+    /// blocks required to fill `okm` can not be represented by the counter
+    /// (or exceeds `2^32 - 1` if counter is not used),
+    /// `Err(KbkdfError::CounterRequired)` if counter is not used in counter
+    /// mode and `Err(KbkdfError::InvalidCounterPosition)` if `MiddleFixed`
+    /// position is bigger than length of fixed input data.
+    pub fn derive(&self, mode: KbkdfMode, fixed_input: &[&[u8]], okm: &mut [u8])
+        -> Result<(), KbkdfError>
+    {
+        let n = M::OutputSize::to_usize();
+        let blocks = ((okm.len() + n - 1) / n) as u64;
+        let max_blocks = match self.counter_len {
+            0 => 0xFFFF_FFFF,
+            r => (1u64 << (8*r)) - 1,
+        };
+        if blocks > max_blocks {
+            return Err(KbkdfError::InvalidLength);
+        }
+        match (mode, self.counter_location) {
+            (KbkdfMode::Counter, CounterLocation::None) => {
+                return Err(KbkdfError::CounterRequired);
+            },
+            (_, CounterLocation::MiddleFixed(pos)) => {
+                let len = fixed_input.iter().fold(0, |acc, p| acc + p.len());
+                if pos > len {
+                    return Err(KbkdfError::InvalidCounterPosition);
+                }
+            },
+            _ => (),
+        }
+
+        let mut prev: Option<GenericArray<u8, M::OutputSize>> = None;
+        let mut a: Option<GenericArray<u8, M::OutputSize>> = None;
+        for (i, chunk) in okm.chunks_mut(n).enumerate() {
+            let buf = to_be_bytes(i as u32 + 1);
+            let counter = &buf[4 - self.counter_len..];
+
+            // compute A(i) in double-pipeline mode
+            if mode == KbkdfMode::DoublePipeline {
+                let mut prf = self.prf.clone();
+                match a {
+                    Some(ref a) => prf.input(a),
+                    None => input_fixed(&mut prf, fixed_input, None),
+                }
+                a = Some(prf.finalize().code());
+            }
+
+            let iter_var: &[u8] = match (mode, prev.as_ref(), a.as_ref()) {
+                (KbkdfMode::Counter, _, _) => &[],
+                (KbkdfMode::Feedback(_), Some(prev), _) => prev,
+                (KbkdfMode::Feedback(iv), None, _) => iv,
+                (KbkdfMode::DoublePipeline, _, Some(a)) => a,
+                (KbkdfMode::DoublePipeline, _, None) => unreachable!(),
+            };
+
+            let mut prf = self.prf.clone();
+            if self.counter_location == CounterLocation::BeforeIter {
+                prf.input(counter);
+            }
+            prf.input(iter_var);
+            match self.counter_location {
+                CounterLocation::BeforeFixed => {
+                    prf.input(counter);
+                    input_fixed(&mut prf, fixed_input, None);
+                },
+                CounterLocation::AfterFixed => {
+                    input_fixed(&mut prf, fixed_input, None);
+                    prf.input(counter);
+                },
+                CounterLocation::MiddleFixed(pos) => {
+                    input_fixed(&mut prf, fixed_input, Some((pos, counter)));
+                },
+                _ => input_fixed(&mut prf, fixed_input, None),
+            }
+
+            let block = prf.finalize().code();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+            prev = Some(block);
+        }
+        Ok(())
+    }
+
+    /// Derive key material into `okm` using fixed input data encoded as
+    /// recommended by SP 800-108: `Label || 0x00 || Context || [L]_32`,
+    /// where `L` is length of `okm` in bits encoded as 32 bit big endian
+    /// integer.
+    ///
+    /// Returns error in the same cases as `derive` or
+    /// `Err(KbkdfError::InvalidLength)` if length of `okm` in bits does not
+    /// fit into 32 bits.
+    pub fn derive_key(&self, mode: KbkdfMode, label: &[u8], context: &[u8],
+        okm: &mut [u8]) -> Result<(), KbkdfError>
+    {
+        let bits = (okm.len() as u64)*8;
+        if bits > 0xFFFF_FFFF {
+            return Err(KbkdfError::InvalidLength);
+        }
+        let l = to_be_bytes(bits as u32);
+        self.derive(mode, &[label, &[0], context, &l], okm)
+    }
+}
@@ -0,0 +1,51 @@
+//! This crate provides generic implementations of key derivation functions
+//! (KDFs) built on top of traits from the `digest` and `crypto-mac` crates.
+//!
+//! Currently the following KDFs are implemented:
+//! - `Hkdf`: HMAC-based Extract-and-Expand KDF ([RFC 5869])
+//! - `pbkdf2`: Password-Based KDF 2 over any `Mac` ([RFC 8018])
+//! - `Kbkdf`: key-based KDF in counter, feedback and double-pipeline
+//!   modes over any `Mac` ([NIST SP 800-108])
+//! - `x963_kdf`: ANSI X9.63 KDF over any `Digest` ([SEC 1])
+//! - `concat_kdf` and `concat_kdf_mac`: one-step KDF over any `Digest` or
+//!   `Mac` ([NIST SP 800-56C])
+//! - `tls` module: TLS 1.2 PRF ([RFC 5246]), TLS 1.3 `HKDF-Expand-Label`,
+//!   `Derive-Secret` and key schedule ([RFC 8446])
+//!
+//! Enabling `parallel` feature adds `pbkdf2_par`, which computes blocks of
+//! the derived key using multiple threads. This feature depends on `rayon`
+//! 1.x, recent releases of which require a Rust version newer than the one
+//! supported by the rest of the crate.
+//!
+//! [RFC 5869]: https://tools.ietf.org/html/rfc5869
+//! [RFC 8018]: https://tools.ietf.org/html/rfc8018
+//! [NIST SP 800-108]: https://doi.org/10.6028/NIST.SP.800-108
+//! [SEC 1]: https://www.secg.org/sec1-v2.pdf
+//! [NIST SP 800-56C]: https://doi.org/10.6028/NIST.SP.800-56Cr1
+//! [RFC 5246]: https://tools.ietf.org/html/rfc5246
+//! [RFC 8446]: https://tools.ietf.org/html/rfc8446
+#![no_std]
+pub extern crate crypto_mac;
+pub extern crate digest;
+
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+mod errors;
+mod hkdf;
+mod pbkdf2;
+mod kbkdf;
+mod one_step;
+pub mod tls;
+#[cfg(feature = "dev")]
+pub mod dev;
+
+pub use errors::{InvalidLength, InvalidPrkLength, KbkdfError};
+pub use hkdf::Hkdf;
+pub use pbkdf2::pbkdf2;
+#[cfg(feature = "parallel")]
+pub use pbkdf2::pbkdf2_par;
+pub use kbkdf::{Kbkdf, KbkdfMode, CounterLocation};
+pub use one_step::{x963_kdf, concat_kdf, concat_kdf_mac};
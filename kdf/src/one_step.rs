@@ -0,0 +1,98 @@
+use crypto_mac::Mac;
+use digest::Digest;
+use digest::generic_array::typenum::Unsigned;
+use errors::InvalidLength;
+
+#[inline(always)]
+fn to_be_bytes(n: u32) -> [u8; 4] {
+    [(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8]
+}
+
+/// Check that number of `n` bytes blocks required to fill `okm` fits into
+/// 32 bit counter.
+fn check_len(okm: &[u8], n: usize) -> Result<(), InvalidLength> {
+    let blocks = ((okm.len() + n - 1) / n) as u64;
+    if blocks > 0xFFFF_FFFF {
+        Err(InvalidLength)
+    } else {
+        Ok(())
+    }
+}
+
+/// [ANSI X9.63][1] KDF generic over hash function.
+///
+/// Output key material is computed as concatenation of
+/// `Hash(Z || Counter || SharedInfo)` blocks, where `Counter` is 32 bit big
+/// endian integer starting from 1.
+///
+/// Returns `Err(InvalidLength)` if the number of hash blocks required to
+/// fill `okm` exceeds `2^32 - 1`.
+///
+/// [1]: https://www.secg.org/sec1-v2.pdf#page=38
+pub fn x963_kdf<D: Digest>(z: &[u8], shared_info: &[u8], okm: &mut [u8])
+    -> Result<(), InvalidLength>
+{
+    let n = D::OutputSize::to_usize();
+    check_len(okm, n)?;
+    for (i, chunk) in okm.chunks_mut(n).enumerate() {
+        let mut d = D::default();
+        d.process(z);
+        d.process(&to_be_bytes(i as u32 + 1));
+        d.process(shared_info);
+        chunk.copy_from_slice(&d.finalize_fixed()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// [NIST SP 800-56C][1] one-step KDF (also known as Concat KDF) generic
+/// over hash function.
+///
+/// Output key material is computed as concatenation of
+/// `Hash(Counter || Z || FixedInfo)` blocks, where `Counter` is 32 bit big
+/// endian integer starting from 1.
+///
+/// Returns `Err(InvalidLength)` if the number of hash blocks required to
+/// fill `okm` exceeds `2^32 - 1`.
+///
+/// [1]: https://doi.org/10.6028/NIST.SP.800-56Cr1
+pub fn concat_kdf<D: Digest>(z: &[u8], fixed_info: &[u8], okm: &mut [u8])
+    -> Result<(), InvalidLength>
+{
+    let n = D::OutputSize::to_usize();
+    check_len(okm, n)?;
+    for (i, chunk) in okm.chunks_mut(n).enumerate() {
+        let mut d = D::default();
+        d.process(&to_be_bytes(i as u32 + 1));
+        d.process(z);
+        d.process(fixed_info);
+        chunk.copy_from_slice(&d.finalize_fixed()[..chunk.len()]);
+    }
+    Ok(())
+}
+
+/// [NIST SP 800-56C][1] one-step KDF with MAC as auxiliary function (e.g.
+/// HMAC or KMAC).
+///
+/// `mac` must be initialized with salt, for HMAC the default all-zero salt
+/// can be set by initializing it with an empty key. Output key material is
+/// computed as concatenation of `MAC(Counter || Z || FixedInfo)` blocks,
+/// where `Counter` is 32 bit big endian integer starting from 1.
+///
+/// Returns `Err(InvalidLength)` if the number of MAC blocks required to
+/// fill `okm` exceeds `2^32 - 1`.
+///
+/// [1]: https://doi.org/10.6028/NIST.SP.800-56Cr1
+pub fn concat_kdf_mac<M: Mac + Clone>(
+    mac: &M, z: &[u8], fixed_info: &[u8], okm: &mut [u8],
+) -> Result<(), InvalidLength> {
+    let n = M::OutputSize::to_usize();
+    check_len(okm, n)?;
+    for (i, chunk) in okm.chunks_mut(n).enumerate() {
+        let mut m = mac.clone();
+        m.input(&to_be_bytes(i as u32 + 1));
+        m.input(z);
+        m.input(fixed_info);
+        chunk.copy_from_slice(&m.finalize().code()[..chunk.len()]);
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crypto_mac::{InvalidKey, Mac};
+use digest::generic_array::typenum::Unsigned;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[inline(always)]
+fn xor(res: &mut [u8], salt: &[u8]) {
+    debug_assert!(salt.len() >= res.len());
+    for (a, b) in res.iter_mut().zip(salt.iter()) {
+        *a ^= *b;
+    }
+}
+
+/// Compute `i`-th block of the derived key.
+#[inline(always)]
+fn pbkdf2_body<M: Mac + Clone>(
+    i: u32, chunk: &mut [u8], prf: &M, salt: &[u8], rounds: usize,
+) {
+    for v in chunk.iter_mut() { *v = 0; }
+
+    let mut prf = prf.clone();
+    prf.input(salt);
+    let n = i + 1;
+    prf.input(&[(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8]);
+    let mut u = prf.finalize_reset().code();
+    xor(chunk, &u);
+
+    for _ in 1..rounds {
+        prf.input(&u);
+        u = prf.finalize_reset().code();
+        xor(chunk, &u);
+    }
+}
+
+/// Generic implementation of [PBKDF2][1] over `Mac`, which is used as a
+/// pseudorandom function keyed with `password`.
+///
+/// Derived key is computed block by block and written into `res`, so its
+/// length determines length of the derived key.
+///
+/// Returns error if `Mac` does not accept `password` as a key.
+///
+/// # Panics
+/// If `rounds` is equal to zero.
+///
+/// [1]: https://tools.ietf.org/html/rfc8018#section-5.2
+pub fn pbkdf2<M: Mac + Clone>(
+    password: &[u8], salt: &[u8], rounds: usize, res: &mut [u8],
+) -> Result<(), InvalidKey> {
+    assert!(rounds != 0, "number of rounds must be positive");
+    let n = M::OutputSize::to_usize();
+    let prf = M::new_varkey(password)?;
+    for (i, chunk) in res.chunks_mut(n).enumerate() {
+        pbkdf2_body(i as u32, chunk, &prf, salt, rounds);
+    }
+    Ok(())
+}
+
+/// Same as `pbkdf2`, but blocks of the derived key are computed in parallel
+/// using multiple threads.
+///
+/// Available only with enabled `parallel` feature.
+#[cfg(feature = "parallel")]
+pub fn pbkdf2_par<M: Mac + Clone + Sync>(
+    password: &[u8], salt: &[u8], rounds: usize, res: &mut [u8],
+) -> Result<(), InvalidKey> {
+    assert!(rounds != 0, "number of rounds must be positive");
+    let n = M::OutputSize::to_usize();
+    let prf = M::new_varkey(password)?;
+    res.par_chunks_mut(n).enumerate().for_each(|(i, chunk)| {
+        pbkdf2_body(i as u32, chunk, &prf, salt, rounds);
+    });
+    Ok(())
+}
@@ -0,0 +1,310 @@
+//! Key derivation functions used by TLS 1.2 ([RFC 5246]) and TLS 1.3
+//! ([RFC 8446]).
+//!
+//! TLS 1.3 key schedule is represented by three types corresponding to its
+//! stages: `EarlySecret`, `HandshakeSecret` and `MasterSecret`. Each stage
+//! can derive secrets specific to it and is consumed on transition to the
+//! next one. Transcript hashes are computed by caller and passed as
+//! byte slices.
+//!
+//! [RFC 5246]: https://tools.ietf.org/html/rfc5246
+//! [RFC 8446]: https://tools.ietf.org/html/rfc8446
+use crypto_mac::{InvalidKey, Mac};
+use digest::{BlockInput, FixedOutput, Input};
+use digest::generic_array::GenericArray;
+use digest::generic_array::typenum::Unsigned;
+use errors::InvalidLength;
+use hkdf::Hkdf;
+
+/// Secret with length equal to the hash function output size
+pub type Secret<D> = GenericArray<u8, <D as FixedOutput>::OutputSize>;
+
+/// TLS 1.2 `P_hash` data expansion function generic over `Mac` keyed with
+/// secret. Concatenation of `seed` parts is used as seed.
+pub fn p_hash<M: Mac + Clone>(mac: &M, seed: &[&[u8]], out: &mut [u8]) {
+    let n = M::OutputSize::to_usize();
+    let mut a: Option<GenericArray<u8, M::OutputSize>> = None;
+    for chunk in out.chunks_mut(n) {
+        // A(i) = HMAC_hash(secret, A(i-1)), where A(0) = seed
+        let mut m = mac.clone();
+        match a {
+            Some(ref a) => m.input(a),
+            None => {
+                for part in seed { m.input(part); }
+            },
+        }
+        let next = m.finalize().code();
+
+        let mut m = mac.clone();
+        m.input(&next);
+        for part in seed { m.input(part); }
+        let block = m.finalize().code();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        a = Some(next);
+    }
+}
+
+/// TLS 1.2 pseudorandom function: `PRF(secret, label, seed) =
+/// P_<hash>(secret, label + seed)`.
+///
+/// Returns `Err(InvalidKey)` if `M` does not accept `secret` as a key.
+pub fn tls12_prf<M: Mac + Clone>(
+    secret: &[u8], label: &[u8], seed: &[u8], out: &mut [u8],
+) -> Result<(), InvalidKey> {
+    let mac = M::new_varkey(secret)?;
+    p_hash(&mac, &[label, seed], out);
+    Ok(())
+}
+
+/// TLS 1.3 `HKDF-Expand-Label` function, writes output into `out`.
+///
+/// Prefix `"tls13 "` is prepended to `label` automatically.
+///
+/// Returns `Err(InvalidLength)` if `label` is longer than 249 bytes,
+/// `context` is longer than 255 bytes, or if length of `out` is bigger
+/// than `255*HashLen`.
+pub fn hkdf_expand_label<D>(
+    secret: &Secret<D>, label: &[u8], context: &[u8], out: &mut [u8],
+) -> Result<(), InvalidLength>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    const PREFIX: &[u8] = b"tls13 ";
+    if label.len() > 255 - PREFIX.len() || context.len() > 255 {
+        return Err(InvalidLength);
+    }
+    if out.len() > 0xFFFF {
+        return Err(InvalidLength);
+    }
+    let hkdf = Hkdf::<D>::from_prk(secret).expect("PRK has valid length");
+    let len = [(out.len() >> 8) as u8, out.len() as u8];
+    let label_len = [(PREFIX.len() + label.len()) as u8];
+    let context_len = [context.len() as u8];
+    let info = [&len[..], &label_len, PREFIX, label, &context_len, context];
+    hkdf.expand_multi_info(&info, out)
+}
+
+/// TLS 1.3 `Derive-Secret` function.
+///
+/// Unlike the RFC 8446 definition it accepts transcript hash of messages
+/// instead of messages themselves.
+///
+/// # Panics
+/// If `label` is longer than 249 bytes or `transcript_hash` is longer than
+/// 255 bytes.
+pub fn derive_secret<D>(
+    secret: &Secret<D>, label: &[u8], transcript_hash: &[u8],
+) -> Secret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    let mut res = Secret::<D>::default();
+    hkdf_expand_label::<D>(secret, label, transcript_hash, &mut res)
+        .expect("invalid label or transcript hash length");
+    res
+}
+
+/// Derive traffic key and IV from traffic secret. Lengths of derived
+/// values are determined by lengths of `key` and `iv`.
+///
+/// Returns `Err(InvalidLength)` if length of `key` or `iv` is bigger than
+/// `255*HashLen`.
+pub fn traffic_keys<D>(secret: &Secret<D>, key: &mut [u8], iv: &mut [u8])
+    -> Result<(), InvalidLength>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    hkdf_expand_label::<D>(secret, b"key", &[], key)?;
+    hkdf_expand_label::<D>(secret, b"iv", &[], iv)
+}
+
+/// Derive key used for computation of `Finished` message from base key
+/// (handshake traffic secret of the sender).
+pub fn finished_key<D>(secret: &Secret<D>) -> Secret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    derive_secret::<D>(secret, b"finished", &[])
+}
+
+/// Derive the next generation of application traffic secret for use
+/// after the `KeyUpdate` message.
+pub fn next_traffic_secret<D>(secret: &Secret<D>) -> Secret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    derive_secret::<D>(secret, b"traffic upd", &[])
+}
+
+/// Derive PSK associated with session ticket from resumption master
+/// secret and ticket nonce.
+///
+/// # Panics
+/// If `ticket_nonce` is longer than 255 bytes.
+pub fn resumption_psk<D>(res_master_secret: &Secret<D>, ticket_nonce: &[u8])
+    -> Secret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    derive_secret::<D>(res_master_secret, b"resumption", ticket_nonce)
+}
+
+/// Compute `Derive-Secret(secret, "derived", "")` used as salt for
+/// the next stage of the key schedule.
+fn derived<D>(secret: &Secret<D>) -> Secret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    let empty_hash = D::default().finalize_fixed();
+    derive_secret::<D>(secret, b"derived", &empty_hash)
+}
+
+/// The first stage of TLS 1.3 key schedule holding early secret.
+#[derive(Clone)]
+pub struct EarlySecret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    secret: Secret<D>,
+}
+
+impl<D> EarlySecret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    /// Compute early secret from pre-shared key.
+    ///
+    /// If `psk` is `None`, string of `HashLen` zeros is used.
+    pub fn new(psk: Option<&[u8]>) -> Self {
+        let zeros = Secret::<D>::default();
+        let (secret, _) = Hkdf::<D>::extract(None, psk.unwrap_or(&zeros));
+        EarlySecret { secret }
+    }
+
+    /// Get early secret value
+    pub fn secret(&self) -> &Secret<D> {
+        &self.secret
+    }
+
+    /// Derive binder key for external PSK (`"ext binder"`)
+    pub fn ext_binder_key(&self) -> Secret<D> {
+        let empty_hash = D::default().finalize_fixed();
+        derive_secret::<D>(&self.secret, b"ext binder", &empty_hash)
+    }
+
+    /// Derive binder key for resumption PSK (`"res binder"`)
+    pub fn res_binder_key(&self) -> Secret<D> {
+        let empty_hash = D::default().finalize_fixed();
+        derive_secret::<D>(&self.secret, b"res binder", &empty_hash)
+    }
+
+    /// Derive `client_early_traffic_secret` using hash of `ClientHello`
+    pub fn client_early_traffic_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"c e traffic", transcript_hash)
+    }
+
+    /// Derive `early_exporter_master_secret` using hash of `ClientHello`
+    pub fn early_exporter_master_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"e exp master", transcript_hash)
+    }
+
+    /// Advance key schedule to the handshake stage using (EC)DHE shared
+    /// secret.
+    ///
+    /// If `shared_secret` is `None` (i.e. PSK-only key exchange), string of
+    /// `HashLen` zeros is used.
+    pub fn into_handshake_secret(self, shared_secret: Option<&[u8]>)
+        -> HandshakeSecret<D>
+    {
+        let salt = derived::<D>(&self.secret);
+        let zeros = Secret::<D>::default();
+        let ikm = shared_secret.unwrap_or(&zeros);
+        let (secret, _) = Hkdf::<D>::extract(Some(&salt), ikm);
+        HandshakeSecret { secret }
+    }
+}
+
+/// The second stage of TLS 1.3 key schedule holding handshake secret.
+#[derive(Clone)]
+pub struct HandshakeSecret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    secret: Secret<D>,
+}
+
+impl<D> HandshakeSecret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    /// Get handshake secret value
+    pub fn secret(&self) -> &Secret<D> {
+        &self.secret
+    }
+
+    /// Derive `client_handshake_traffic_secret` using hash of messages
+    /// from `ClientHello` to `ServerHello`
+    pub fn client_handshake_traffic_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"c hs traffic", transcript_hash)
+    }
+
+    /// Derive `server_handshake_traffic_secret` using hash of messages
+    /// from `ClientHello` to `ServerHello`
+    pub fn server_handshake_traffic_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"s hs traffic", transcript_hash)
+    }
+
+    /// Advance key schedule to the master secret stage.
+    pub fn into_master_secret(self) -> MasterSecret<D> {
+        let salt = derived::<D>(&self.secret);
+        let zeros = Secret::<D>::default();
+        let (secret, _) = Hkdf::<D>::extract(Some(&salt), &zeros);
+        MasterSecret { secret }
+    }
+}
+
+/// The final stage of TLS 1.3 key schedule holding master secret.
+#[derive(Clone)]
+pub struct MasterSecret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    secret: Secret<D>,
+}
+
+impl<D> MasterSecret<D>
+    where D: Input + BlockInput + FixedOutput + Default + Clone
+{
+    /// Get master secret value
+    pub fn secret(&self) -> &Secret<D> {
+        &self.secret
+    }
+
+    /// Derive `client_application_traffic_secret_0` using hash of messages
+    /// from `ClientHello` to server `Finished`
+    pub fn client_application_traffic_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"c ap traffic", transcript_hash)
+    }
+
+    /// Derive `server_application_traffic_secret_0` using hash of messages
+    /// from `ClientHello` to server `Finished`
+    pub fn server_application_traffic_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"s ap traffic", transcript_hash)
+    }
+
+    /// Derive `exporter_master_secret` using hash of messages from
+    /// `ClientHello` to server `Finished`
+    pub fn exporter_master_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"exp master", transcript_hash)
+    }
+
+    /// Derive `resumption_master_secret` using hash of messages from
+    /// `ClientHello` to client `Finished`
+    pub fn resumption_master_secret(&self, transcript_hash: &[u8])
+        -> Secret<D>
+    {
+        derive_secret::<D>(&self.secret, b"res master", transcript_hash)
+    }
+}
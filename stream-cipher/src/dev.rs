@@ -24,6 +24,16 @@ macro_rules! new_core_test {
                     mode.apply_keystream(chunk);
                 }
                 assert_eq!(pt, &ciphertext[..]);
+
+                // check that buffer-to-buffer processing gives the same
+                // result as in-place
+                let mut mode = <$cipher>::new(key, iv);
+                let mut ct = vec![0; plaintext.len()];
+                let chunks = plaintext.chunks(i).zip(ct.chunks_mut(i));
+                for (input, output) in chunks {
+                    mode.apply_keystream_b2b(input, output);
+                }
+                assert_eq!(ct, &ciphertext[..]);
             }
         }
     }
@@ -56,6 +66,49 @@ macro_rules! new_seek_test {
                 mode.seek(i as u64);
                 mode.apply_keystream(&mut pt);
                 assert_eq!(pt, &ciphertext[i..]);
+
+                let mut ct = vec![0; pt.len()];
+                mode.seek(i as u64);
+                mode.apply_keystream_b2b(&plaintext[i..], &mut ct);
+                assert_eq!(ct, &ciphertext[i..]);
+            }
+        }
+    }
+}
+
+/// Test asynchronous stream cipher, i.e. `AsyncStreamCipher::encrypt` and
+/// `AsyncStreamCipher::decrypt` methods
+#[macro_export]
+macro_rules! new_async_test {
+    ($name:ident, $cipher:ty, $test_name:expr) => {
+        #[test]
+        fn $name() {
+            use stream_cipher::generic_array::GenericArray;
+            use stream_cipher::{AsyncStreamCipher, NewFixStreamCipher};
+
+            let key = GenericArray::from_slice(include_bytes!(
+                concat!("data/", $test_name, ".key.bin")));
+            let iv = GenericArray::from_slice(include_bytes!(
+                concat!("data/", $test_name, ".iv.bin")));
+            let plaintext = include_bytes!(
+                concat!("data/", $test_name, ".plaintext.bin"));
+            let ciphertext = include_bytes!(
+                concat!("data/", $test_name, ".ciphertext.bin"));
+
+            for i in 1..256 {
+                let mut mode = <$cipher>::new(key, iv);
+                let mut pt = plaintext.to_vec();
+                for chunk in pt.chunks_mut(i) {
+                    mode.encrypt(chunk);
+                }
+                assert_eq!(pt, &ciphertext[..]);
+
+                let mut mode = <$cipher>::new(key, iv);
+                let mut ct = ciphertext.to_vec();
+                for chunk in ct.chunks_mut(i) {
+                    mode.decrypt(chunk);
+                }
+                assert_eq!(ct, &plaintext[..]);
             }
         }
     }
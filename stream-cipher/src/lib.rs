@@ -50,6 +50,40 @@ pub trait StreamCipherCore {
     /// If end of the keystream will be achieved with the given data length,
     /// method will return `Err(LoopError)` without modifiyng provided `data`.
     fn try_apply_keystream(&mut self, data: &mut [u8]) -> Result<(), LoopError>;
+
+    /// Apply keystream to the data from `input` and write result into
+    /// `output`.
+    ///
+    /// # Panics
+    /// If `input` and `output` have different lengths or if end of the
+    /// keystream will be achieved with the given data length.
+    #[inline]
+    fn apply_keystream_b2b(&mut self, input: &[u8], output: &mut [u8]) {
+        self.try_apply_keystream_b2b(input, output)
+            .expect("stream cipher loop detected")
+    }
+
+    /// Apply keystream to the data from `input` and write result into
+    /// `output`, but return an error if end of a keystream will be reached.
+    ///
+    /// If end of the keystream will be achieved with the given data length,
+    /// method will return `Err(LoopError)` without advancing the keystream.
+    /// Unlike with `try_apply_keystream`, content of `output` is unspecified
+    /// in this case: default implementation copies `input` into `output`
+    /// before applying keystream in-place, so on error `output` will contain
+    /// a copy of `input`. Implementations which can detect the end of the
+    /// keystream in advance are encouraged to leave `output` unmodified.
+    ///
+    /// # Panics
+    /// If `input` and `output` have different lengths.
+    #[inline]
+    fn try_apply_keystream_b2b(&mut self, input: &[u8], output: &mut [u8])
+        -> Result<(), LoopError>
+    {
+        assert_eq!(input.len(), output.len());
+        output.copy_from_slice(input);
+        self.try_apply_keystream(output)
+    }
 }
 
 /// Synchronous stream cipher seeking trait
@@ -60,7 +94,20 @@ pub trait StreamCipherSeek {
     fn seek(&mut self, pos: u64);
 }
 
-/// Synchronous stream cipher creation trait
+/// Asynchronous stream cipher trait, e.g. CFB mode.
+///
+/// Unlike synchronous stream ciphers, keystream depends on the previously
+/// processed ciphertext, so encryption and decryption are different
+/// operations.
+pub trait AsyncStreamCipher {
+    /// Encrypt data in place.
+    fn encrypt(&mut self, data: &mut [u8]);
+
+    /// Decrypt data in place.
+    fn decrypt(&mut self, data: &mut [u8]);
+}
+
+/// Stream cipher creation trait
 pub trait NewFixStreamCipher {
     /// Key size in bytes
     type KeySize: ArrayLength<u8>;